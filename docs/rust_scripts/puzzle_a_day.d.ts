@@ -1,21 +1,37 @@
 /* tslint:disable */
 /* eslint-disable */
 
+export function board_svg(custom_board: string): string;
+
 export function get_board_options(): any;
 
-export function solve(month: number, day: number, week_day: number, custom_board: string): any[];
+export function solution_record(date: string, custom_board: string): any;
+
+export function solution_svg(custom_board: string, tiles: any[], hidden: Uint32Array, colors: string[]): string;
+
+export function solve(date: string, custom_board: string): any[];
+
+export function solve_labels(labels: string[], custom_board: string): any[];
+
+export function solve_with_overrides(date: string, custom_board: string, overrides: any): any[];
 
 export type InitInput = RequestInfo | URL | Response | BufferSource | WebAssembly.Module;
 
 export interface InitOutput {
   readonly memory: WebAssembly.Memory;
-  readonly solve: (a: number, b: number, c: number, d: number, e: number) => [number, number];
+  readonly board_svg: (a: number, b: number) => [number, number];
   readonly get_board_options: () => any;
+  readonly solution_record: (a: number, b: number, c: number, d: number) => [number, number, number];
+  readonly solution_svg: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number) => [number, number, number, number];
+  readonly solve: (a: number, b: number, c: number, d: number) => [number, number, number, number];
+  readonly solve_labels: (a: number, b: number, c: number, d: number) => [number, number];
+  readonly solve_with_overrides: (a: number, b: number, c: number, d: number, e: any) => [number, number, number, number];
   readonly __wbindgen_malloc: (a: number, b: number) => number;
   readonly __wbindgen_realloc: (a: number, b: number, c: number, d: number) => number;
   readonly __wbindgen_exn_store: (a: number) => void;
   readonly __externref_table_alloc: () => number;
   readonly __wbindgen_externrefs: WebAssembly.Table;
+  readonly __externref_table_dealloc: (a: number) => void;
   readonly __externref_drop_slice: (a: number, b: number) => void;
   readonly __wbindgen_free: (a: number, b: number, c: number) => void;
   readonly __wbindgen_start: () => void;
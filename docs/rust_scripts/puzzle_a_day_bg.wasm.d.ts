@@ -1,13 +1,19 @@
 /* tslint:disable */
 /* eslint-disable */
 export const memory: WebAssembly.Memory;
-export const solve: (a: number, b: number, c: number, d: number, e: number) => [number, number];
+export const board_svg: (a: number, b: number) => [number, number];
 export const get_board_options: () => any;
+export const solution_record: (a: number, b: number, c: number, d: number) => [number, number, number];
+export const solution_svg: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number) => [number, number, number, number];
+export const solve: (a: number, b: number, c: number, d: number) => [number, number, number, number];
+export const solve_labels: (a: number, b: number, c: number, d: number) => [number, number];
+export const solve_with_overrides: (a: number, b: number, c: number, d: number, e: any) => [number, number, number, number];
 export const __wbindgen_malloc: (a: number, b: number) => number;
 export const __wbindgen_realloc: (a: number, b: number, c: number, d: number) => number;
 export const __wbindgen_exn_store: (a: number) => void;
 export const __externref_table_alloc: () => number;
 export const __wbindgen_externrefs: WebAssembly.Table;
+export const __externref_table_dealloc: (a: number) => void;
 export const __externref_drop_slice: (a: number, b: number) => void;
 export const __wbindgen_free: (a: number, b: number, c: number) => void;
 export const __wbindgen_start: () => void;
@@ -1,4 +1,4 @@
-import init, { solve, get_board_options } from "./rust_scripts/puzzle_a_day.js";
+import init, { get_board_options, solution_svg } from "./rust_scripts/puzzle_a_day.js";
 
 let wasmReady = false;
 init().then(() => {
@@ -6,20 +6,8 @@ init().then(() => {
 });
 
 let PIECES = [];
-const COLORS = [
-  "#FF5733",
-  "#33FF57",
-  "#3357FF",
-  "#F333FF",
-  "#33FFF5",
-  "#F5FF33",
-  "#FF33A8",
-  "#A833FF",
-  "#33FFA8",
-  "#FFA833",
-  "#8D33FF",
-  "#33D1FF",
-];
+let BOARD = ""; // the board PIECES were solved for
+let SHOW_ORDER = []; // random order to reveal pieces in, picked once per solution
 
 const worker = new Worker("./worker.js", { type: "module" });
 worker.onmessage = (event) => {
@@ -30,10 +18,20 @@ worker.onmessage = (event) => {
     alert(error);
   } else {
     PIECES = result;
+    SHOW_ORDER = shuffled_indices(PIECES.length);
     update_solution();
   }
 };
 
+const shuffled_indices = (length) => {
+  const indices = [...Array(length).keys()];
+  for (let i = indices.length - 1; i > 0; i--) {
+    const j = Math.floor(Math.random() * (i + 1));
+    [indices[i], indices[j]] = [indices[j], indices[i]];
+  }
+  return indices;
+};
+
 export const update_solution = () => {
   const solutionDiv = document.getElementById("solution");
   solutionDiv.innerHTML = "";
@@ -42,69 +40,16 @@ export const update_solution = () => {
     return;
   }
 
+  // the solver draws the board; every piece not yet revealed is left off
   const showPieces = parseInt(document.getElementById("show-pieces").value, 10);
-
-  // find boundary rectangle
-  const max_x = Math.max(
-    ...PIECES.map(({ coordinates }) =>
-      Math.max(...coordinates.map(({ x }) => x))
-    )
-  );
-  const max_y = Math.max(
-    ...PIECES.map(({ coordinates }) =>
-      Math.max(...coordinates.map(({ y }) => y))
-    )
-  );
-
-  // build a map of piece index -> color
-  const colorMap = {};
-  for (let i = 0; i < PIECES.length; i++) {
-    colorMap[i] = COLORS[i % COLORS.length];
-  }
-
-  // then filter pieces based on showPieces
-  const visiblePieces = PIECES.slice(
-    0,
-    showPieces >= PIECES.length ? PIECES.length : showPieces
-  );
-
-  // define the display grid
-  const displayGrid = [];
-  for (let y = 0; y <= max_y; y++) {
-    displayGrid[y] = [];
-    for (let x = 0; x <= max_x; x++) {
-      displayGrid[y][x] = "#0000"; // default transparent
-    }
-  }
-
-  // populate the displayGrid with piece colors
-  for (let i = 0; i < visiblePieces.length; i++) {
-    for (let coor of visiblePieces[i].coordinates) {
-      const { x, y } = coor;
-      displayGrid[y][x] = colorMap[i];
-    }
-  }
-
-  // then grab screen size, do some calculations (we need square cells, max width of entire board = min(screen width, 800px))
-  const screenWidth = window.innerWidth;
-  const maxBoardWidth = Math.min(screenWidth - 40, 800); // 40px padding
-  const cellSize = Math.floor(maxBoardWidth / (max_x + 1));
-
-  // then update the html
-  let html = `<div style="display: grid; grid-template-columns: repeat(${
-    max_x + 1
-  }, ${cellSize}px); grid-template-rows: repeat(${
-    max_y + 1
-  }, ${cellSize}px); gap: 1px;">`;
-  for (let y = 0; y <= max_y; y++) {
-    for (let x = 0; x <= max_x; x++) {
-      const color = displayGrid[y][x];
-      html += `<div style="width: ${cellSize}px; height: ${cellSize}px; background-color: ${color};"></div>`;
-    }
-  }
-  html += `</div>`;
-
-  solutionDiv.innerHTML = html;
+  const hidden = Uint32Array.from(SHOW_ORDER.slice(showPieces));
+  solutionDiv.innerHTML = solution_svg(BOARD, PIECES, hidden, []);
+
+  // max width of the board = min(screen width, 800px)
+  const svg = solutionDiv.querySelector("svg");
+  const maxBoardWidth = Math.min(window.innerWidth - 40, 800); // 40px padding
+  svg.setAttribute("width", maxBoardWidth);
+  svg.removeAttribute("height");
 };
 
 export const trigger_solve = () => {
@@ -114,14 +59,18 @@ export const trigger_solve = () => {
     date: dateElem.value,
     board: boardElem.value,
   });
-  const monthMatch = dateElem.value.match(/-(\d{2})-/);
-  const dayMatch = dateElem.value.match(/-(\d{2})T/);
-  const weekDay = new Date(dateElem.value).getDay();
-
+  BOARD = boardElem.value;
+  // message boards leave one cell per letter uncovered instead of a date
+  if (board_kind() === "message") {
+    worker.postMessage({
+      message: document.getElementById("message").value,
+      custom_board: boardElem.value,
+    });
+    return;
+  }
+  // the solver works out the weekday itself, from the YYYY-MM-DD part
   worker.postMessage({
-    month: monthMatch[1],
-    day: dayMatch[1],
-    week_day: `${weekDay}`,
+    date: dateElem.value.slice(0, 10),
     custom_board: boardElem.value,
   });
 };
@@ -147,10 +96,25 @@ const populate_board_options = async () => {
   const selectElement = document.getElementById("board-select");
   options.forEach((option) => {
     const opt = document.createElement("option");
-    opt.value = option;
-    opt.innerHTML = option;
+    opt.value = option.name;
+    opt.textContent = option.name;
+    opt.dataset.kind = option.kind;
     selectElement.appendChild(opt);
   });
+  update_target_controls();
+};
+
+const board_kind = () => {
+  const selectElement = document.getElementById("board-select");
+  const selected = selectElement.options[selectElement.selectedIndex];
+  return selected ? selected.dataset.kind : "calendar";
+};
+
+// a date for calendar boards, a message for message boards
+const update_target_controls = () => {
+  const message = board_kind() === "message";
+  document.getElementById("date-control").hidden = message;
+  document.getElementById("message-control").hidden = !message;
 };
 
 const set_default_date_time = () => {
@@ -164,6 +128,7 @@ const set_default_date_time = () => {
 const register_label_update = () => {
   const showPiecesInput = document.getElementById("show-pieces");
   showPiecesInput.addEventListener("input", update_pieces_shown_label);
+  document.getElementById("board-select").addEventListener("change", update_target_controls);
 }
 
 window.onload = function () {
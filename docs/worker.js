@@ -1,4 +1,4 @@
-import init, { solve } from "./rust_scripts/puzzle_a_day.js";
+import init, { solve, solve_labels } from "./rust_scripts/puzzle_a_day.js";
 
 let wasmReady = false;
 
@@ -7,7 +7,7 @@ init().then(() => {
 });
 
 self.onmessage = async (event) => {
-  const { month, day, week_day, custom_board } = event.data;
+  const { date, message, custom_board } = event.data;
 
   if (!wasmReady) {
     alert("WASM module not ready");
@@ -15,7 +15,10 @@ self.onmessage = async (event) => {
   }
 
   try {
-    const result = solve(month, day, week_day, custom_board);
+    const result =
+      message === undefined
+        ? solve(date, custom_board)
+        : solve_labels(Array.from(message), custom_board);
     self.postMessage({ result });
   } catch (error) {
     self.postMessage({ error: error.message });
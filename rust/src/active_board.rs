@@ -1,24 +1,102 @@
 use crate::{
-    common::{AllTime, Coordinate, CustomBoard, Tile},
+    common::{Coordinate, CustomBoard, Orientation, PlacedPiece, SolveMode, Tile, Usage, Wrap},
+    lattice::Lattice,
+    polyomino::Polyomino,
     tile_helper::TileHelper,
 };
 use std::collections::HashSet;
+use std::hash::Hash;
 
 pub struct ActiveBoard {
-    tile_sets: Vec<Vec<Tile>>,  // each tile can have eight orientations; one tile set contains all orientations for that tile
+    tile_sets: Vec<Vec<Tile>>,  // each tile can have up to eight orientations; one tile set contains all allowed orientations for that tile
+    tile_set_orientations: Vec<Vec<Orientation>>, // how each tile in the set was turned
+    tile_set_piece: Vec<(usize, usize)>, // index into CustomBoard::pieces(), and which copy of it
+    piece_names: Vec<Option<String>>,    // by CustomBoard::pieces() index
     tile_set_placed: Vec<bool>, // a tile from the set has been placed on the board
+    tile_set_anchor: Vec<Option<Coordinate>>, // where tile[0] of the placed tile ended up
+    copy_of_previous: Vec<bool>, // the tile set is an identical copy of the one before it
+    mode: SolveMode,
+    tile_set_required: Vec<bool>, // the set must be placed for the board to count as solved
+    tile_set_skipped: Vec<bool>,  // decided not to place the set (only in CoverBoard mode)
+    tile_set_group: Vec<Option<usize>>, // index into group_picks
+    group_picks: Vec<usize>,            // exactly this many sets of each group must be placed
+    max_gaps: usize, // open coordinates that may be left uncovered once every tile is placed
+    lattice: Lattice,
+    wrap: Wrap,
+    wrap_min: Coordinate,  // top left corner of the rectangle that wraps around
+    wrap_size: Coordinate, // width and height of that rectangle
     open_coors: Vec<Vec<bool>>, // 16x16 grid of open (true) and closed (false) coordinates
 }
 
-pub struct ActiveTileSet {
-    key: usize,           // an index into ActiveBoard tile_sets / tile_set_placed
-    pub tiles: Vec<Tile>, // all orientations of the tile
+pub struct ActiveTileSet<C = Coordinate> {
+    key: usize,              // an index into the board's tile sets
+    pub tiles: Vec<Vec<C>>,  // all orientations of the tile
+    start_after: Option<C>,  // only try open coordinates after this one
 }
 
-impl ActiveTileSet {
+impl<C> ActiveTileSet<C> {
+    pub fn new(key: usize, tiles: Vec<Vec<C>>, start_after: Option<C>) -> ActiveTileSet<C> {
+        ActiveTileSet {
+            key,
+            tiles,
+            start_after,
+        }
+    }
+
     pub fn key(&self) -> usize {
         self.key
     }
+
+    // identical copies of a piece are interchangeable, so each copy is only placed after the
+    // previous one (in scan order); otherwise every swap of two copies would be searched again
+    pub fn start_after(&self) -> Option<&C> {
+        self.start_after.as_ref()
+    }
+}
+
+// one way a piece fits on the board, for solvers that look at every placement at once
+pub struct Placement<C = Coordinate> {
+    pub set_key: usize,     // the first copy of the piece; any other copies are the sets right after it
+    pub copies: usize,      // how many identical copies of the piece there are
+    pub orientation: usize, // index into the set's tiles
+    pub tile: Vec<C>,       // the oriented tile, moved into place (as given to place_tile)
+    pub cells: Vec<C>,      // the cells it covers
+}
+
+// everything the solver needs from a board, so the same search can pack flat tiles onto a
+// calendar board and polycubes into a box
+pub trait Packing {
+    type Coor: Copy + Eq + Hash;
+    type Placed;
+
+    fn mode(&self) -> SolveMode;
+    // open cells that may be left uncovered once every tile is placed
+    fn max_gaps(&self) -> usize;
+    // every open cell has been covered
+    fn is_covered(&self) -> bool;
+    // are all required sets placed, and has every group had exactly the right number picked?
+    fn usage_satisfied(&self) -> bool;
+    // the first tile set that is neither placed nor skipped
+    fn get_next_tile_set(&self) -> Option<ActiveTileSet<Self::Coor>>;
+    // sizes of the groups of connected open cells
+    fn island_sizes(&self) -> Vec<usize>;
+    fn smallest_unplaced_tile_size(&self) -> usize;
+    // open cells in scan order; None gives the first one
+    fn get_next_open_coor(&self, current_coor: Option<&Self::Coor>) -> Option<Self::Coor>;
+    // move the tile so tile[0] is on the coordinate; false if that would change its shape
+    fn move_tile(&self, tile: &mut [Self::Coor], coor: &Self::Coor) -> bool;
+    // may a tile from this set be placed, given what has been decided so far?
+    fn can_place(&self, set_key: usize) -> bool;
+    fn place_tile(&mut self, set_key: usize, tile: &[Self::Coor]) -> bool;
+    fn remove_tile(&mut self, set_key: usize, tile: &[Self::Coor]);
+    // describe a tile (one of the set's orientations, moved into place) in terms of the board's pieces
+    fn placed_piece(&self, set_key: usize, orientation: usize, tile: &[Self::Coor]) -> Self::Placed;
+    // may this set be left off the board entirely?
+    fn can_skip(&self, set_key: usize) -> bool;
+    fn skip_tile_set(&mut self, set_key: usize);
+    fn unskip_tile_set(&mut self, set_key: usize);
+    // every way each piece fits on the board as it is now; copies of a piece share their placements
+    fn placements(&mut self) -> Vec<Placement<Self::Coor>>;
 }
 
 impl ActiveBoard {
@@ -36,59 +114,109 @@ impl ActiveBoard {
         }
     }
 
-    pub fn from_custom(board: &dyn CustomBoard, pit: &AllTime) -> ActiveBoard {
-        let avoid_points = match board.point_in_time(pit) {
-            Some(coors) => coors,
-            None => {
-                panic!("Invalid point in time for board");
-            }
+    // avoid_points are the cells to leave uncovered (one of CustomBoard::target_alternatives)
+    pub fn from_custom(
+        board: &dyn CustomBoard,
+        avoid_points: &HashSet<Coordinate>,
+        rng: &mut impl rand::Rng,
+    ) -> ActiveBoard {
+
+        let board_coors = board.coors();
+        let wrap_min = Coordinate {
+            x: board_coors.iter().map(|coor| coor.x).min().unwrap_or(0),
+            y: board_coors.iter().map(|coor| coor.y).min().unwrap_or(0),
+        };
+        let wrap_size = Coordinate {
+            x: board_coors.iter().map(|coor| coor.x - wrap_min.x + 1).max().unwrap_or(1),
+            y: board_coors.iter().map(|coor| coor.y - wrap_min.y + 1).max().unwrap_or(1),
         };
 
         let mut open_coors = vec![vec![false; 16]; 16];
-        for coor in board.coors() {
+        for coor in board_coors {
             open_coors[coor.x as usize][coor.y as usize] = true;
         }
-        for coor in &avoid_points {
+        for coor in avoid_points {
             open_coors[coor.x as usize][coor.y as usize] = false;
         }
 
         // shuffle tile order on start, so we get a potentially different solution each time
-        let mut board_tiles = board.tiles().clone();
-        rand::seq::SliceRandom::shuffle(board_tiles.as_mut_slice(), &mut rand::rng());
-
-        let mut tiles: Vec<Vec<Tile>> = Vec::with_capacity(board_tiles.len());
-        for tile in board_tiles {
-            let mut orientations: Vec<Tile> = Vec::with_capacity(8);
-            let mut current_tile = tile.clone();
-            for _ in 0..4 {
-                orientations.push(current_tile.clone());
-                TileHelper::rotate(&mut current_tile);
-            }
-            TileHelper::flip(&mut current_tile);
-            for _ in 0..4 {
-                orientations.push(current_tile.clone());
-                TileHelper::rotate(&mut current_tile);
+        let board_pieces = board.pieces();
+        let mut order: Vec<usize> = (0..board_pieces.len()).collect();
+        rand::seq::SliceRandom::shuffle(order.as_mut_slice(), rng);
+
+        let groups = board.piece_groups();
+        let mode = board.solve_mode();
+        let lattice = board.lattice();
+
+        let mut tiles: Vec<Vec<Tile>> = Vec::with_capacity(board_pieces.len());
+        let mut tile_set_orientations: Vec<Vec<Orientation>> = Vec::with_capacity(board_pieces.len());
+        let mut tile_set_piece: Vec<(usize, usize)> = Vec::with_capacity(board_pieces.len());
+        let mut copy_of_previous: Vec<bool> = Vec::with_capacity(board_pieces.len());
+        let mut tile_set_required: Vec<bool> = Vec::with_capacity(board_pieces.len());
+        let mut tile_set_group: Vec<Option<usize>> = Vec::with_capacity(board_pieces.len());
+        for i in order {
+            let piece = &board_pieces[i];
+            let group = groups.iter().position(|g| g.pieces.contains(&i));
+            let required = match mode {
+                SolveMode::UseAllPieces => true,
+                SolveMode::CoverBoard => group.is_none() && piece.usage == Usage::Required,
+            };
+
+            let (orientations, orientation_tiles): (Vec<Orientation>, Vec<Tile>) =
+                TileHelper::oriented(&piece.tile, piece.symmetry, lattice).into_iter().unzip();
+            for copy in 0..piece.count {
+                tiles.push(orientation_tiles.clone());
+                tile_set_orientations.push(orientations.clone());
+                tile_set_piece.push((i, copy));
+                copy_of_previous.push(copy > 0);
+                tile_set_required.push(required);
+                tile_set_group.push(group);
             }
-            tiles.push(orientations);
         }
 
         ActiveBoard {
+            piece_names: board_pieces
+                .iter()
+                .map(|p| match lattice {
+                    Lattice::Square => Polyomino::name(&p.tile),
+                    Lattice::Hexagonal | Lattice::Triangular => None,
+                })
+                .collect(),
+            tile_set_orientations,
+            tile_set_piece,
             tile_set_placed: vec![false; tiles.len()],
+            tile_set_anchor: vec![None; tiles.len()],
+            tile_set_skipped: vec![false; tiles.len()],
+            copy_of_previous,
+            mode,
+            tile_set_required,
+            tile_set_group,
+            group_picks: groups.iter().map(|g| g.pick).collect(),
+            max_gaps: 0,
             tile_sets: tiles,
+            lattice,
+            wrap: board.wrap(),
+            wrap_min,
+            wrap_size,
             open_coors,
         }
     }
 
-    pub fn get_next_tile_set(&self) -> Option<ActiveTileSet> {
-        for (i, placed) in self.tile_set_placed.iter().enumerate() {
-            if !placed {
-                return Some(ActiveTileSet {
-                    key: i,
-                    tiles: self.tile_sets[i].clone(),
-                });
-            }
+    // where a coordinate that ran off a wrapping edge comes back onto the board
+    fn wrapped(&self, coor: &Coordinate) -> Coordinate {
+        let mut out = *coor;
+        if self.wrap.horizontal() {
+            out.x = self.wrap_min.x + (coor.x - self.wrap_min.x).rem_euclid(self.wrap_size.x);
         }
-        None
+        if self.wrap.vertical() {
+            out.y = self.wrap_min.y + (coor.y - self.wrap_min.y).rem_euclid(self.wrap_size.y);
+        }
+        out
+    }
+
+    // when the tiles can't cover the whole board (eg. a piece went missing), allow some cells to stay open
+    pub fn set_max_gaps(&mut self, max_gaps: usize) {
+        self.max_gaps = max_gaps;
     }
 
     fn get_first_open_coor(&self) -> Option<Coordinate> {
@@ -105,7 +233,143 @@ impl ActiveBoard {
         None
     }
 
-    pub fn get_next_open_coor(&self, current_coor: Option<&Coordinate>) -> Option<Coordinate> {
+    #[allow(dead_code)]
+    pub fn open_coors(&self) -> Vec<Coordinate> {
+        let mut out: Vec<Coordinate> = Vec::new();
+        let mut current = self.get_next_open_coor(None);
+        while let Some(coor) = current {
+            out.push(coor);
+            current = self.get_next_open_coor(Some(&coor));
+        }
+        out
+    }
+
+    pub fn open_coor_count(&self) -> usize {
+        self.open_coors.iter().flatten().filter(|open| **open).count()
+    }
+
+    fn group_placed(&self, group: usize) -> usize {
+        (0..self.tile_sets.len())
+            .filter(|i| self.tile_set_group[*i] == Some(group) && self.tile_set_placed[*i])
+            .count()
+    }
+
+    fn group_undecided(&self, group: usize) -> usize {
+        (0..self.tile_sets.len())
+            .filter(|i| {
+                self.tile_set_group[*i] == Some(group)
+                    && !self.tile_set_placed[*i]
+                    && !self.tile_set_skipped[*i]
+            })
+            .count()
+    }
+
+    pub fn find_islands(&self) -> Vec<Tile> {
+        let mut visited: HashSet<Coordinate> = HashSet::new();
+        let mut islands: Vec<Tile> = Vec::new();
+
+        for x in 0..self.open_coors.len() {
+            for y in 0..self.open_coors[0].len() {
+                let coor = Coordinate {
+                    x: x as i32,
+                    y: y as i32,
+                };
+                if self.open_coors[x][y] && !visited.contains(&coor) {
+                    // found start of new island
+                    let mut island: Tile = Vec::new();
+                    let mut stack: Vec<Coordinate> = vec![coor];
+
+                    while let Some(current) = stack.pop() {
+                        if visited.contains(&current) {
+                            continue;
+                        }
+                        visited.insert(current);
+                        island.push(current);
+
+                        // check neighbors (across the edge, if the board wraps)
+                        for neighbor in self.lattice.neighbors(&current).iter().map(|n| self.wrapped(n)) {
+                            if neighbor.x >= 0
+                                && neighbor.x < self.open_coors.len() as i32
+                                && neighbor.y >= 0
+                                && neighbor.y < self.open_coors[0].len() as i32
+                                && self.open_coors[neighbor.x as usize][neighbor.y as usize]
+                                && !visited.contains(&neighbor)
+                            {
+                                stack.push(neighbor);
+                            }
+                        }
+                    }
+
+                    islands.push(island);
+                }
+            }
+        }
+
+        islands
+    }
+}
+
+impl Packing for ActiveBoard {
+    type Coor = Coordinate;
+    type Placed = PlacedPiece;
+
+    fn mode(&self) -> SolveMode {
+        self.mode
+    }
+
+    fn max_gaps(&self) -> usize {
+        self.max_gaps
+    }
+
+    fn is_covered(&self) -> bool {
+        self.get_first_open_coor().is_none()
+    }
+
+    fn usage_satisfied(&self) -> bool {
+        let required_placed = self
+            .tile_set_required
+            .iter()
+            .zip(self.tile_set_placed.iter())
+            .all(|(required, placed)| !required || *placed);
+        let groups_picked =
+            (0..self.group_picks.len()).all(|group| self.group_placed(group) == self.group_picks[group]);
+        required_placed && groups_picked
+    }
+
+    fn get_next_tile_set(&self) -> Option<ActiveTileSet> {
+        for (i, placed) in self.tile_set_placed.iter().enumerate() {
+            if !placed && !self.tile_set_skipped[i] {
+                let start_after = if self.copy_of_previous[i] {
+                    self.tile_set_anchor[i - 1]
+                } else {
+                    None
+                };
+                return Some(ActiveTileSet::new(i, self.tile_sets[i].clone(), start_after));
+            }
+        }
+        None
+    }
+
+    fn island_sizes(&self) -> Vec<usize> {
+        self.find_islands().iter().map(|island| island.len()).collect()
+    }
+
+    fn smallest_unplaced_tile_size(&self) -> usize {
+        self.tile_sets
+            .iter()
+            .enumerate()
+            .filter_map(|(i, tile_set)| {
+                if !self.tile_set_placed[i] && !self.tile_set_skipped[i] {
+                    Some(tile_set[0].len())
+                } else {
+                    None
+                }
+            })
+            .min()
+            .unwrap_or(usize::MAX)
+    }
+
+    fn get_next_open_coor(&self, current_coor: Option<&Coordinate>) -> Option<Coordinate> {
         let current_coor = match current_coor {
             Some(coor) => coor,
             None => {
@@ -128,113 +392,207 @@ impl ActiveBoard {
         None
     }
 
-    pub fn place_tile(&mut self, set_key: usize, tile: &Tile) -> bool {
+    fn move_tile(&self, tile: &mut [Coordinate], coor: &Coordinate) -> bool {
+        let offset = TileHelper::calc_offset(tile, coor);
+        if !self.lattice.is_valid_offset(&offset) {
+            return false;
+        }
+        TileHelper::translate(tile, &offset);
+        true
+    }
+
+    fn can_place(&self, set_key: usize) -> bool {
+        // copies are placed before skipped copies, so skipping copy 1 and placing copy 2 is never tried
+        if self.copy_of_previous[set_key] && self.tile_set_skipped[set_key - 1] {
+            return false;
+        }
+        match self.tile_set_group[set_key] {
+            Some(group) => self.group_placed(group) < self.group_picks[group],
+            None => true,
+        }
+    }
+
+    fn place_tile(&mut self, set_key: usize, tile: &[Coordinate]) -> bool {
         debug_assert!(!self.tile_set_placed[set_key], "Tile already placed");
 
-        if !TileHelper::is_within_board(tile) {
+        let tile: Tile = tile.iter().map(|coor| self.wrapped(coor)).collect();
+        if !TileHelper::is_within_board(&tile) {
             return false;
         }
 
-        for coor in tile.iter() {
+        for (i, coor) in tile.iter().enumerate() {
             if !self.open_coors[coor.x as usize][coor.y as usize] {
                 return false;
             }
+            // a tile longer than the board is wide would wrap around onto itself
+            if tile[..i].contains(coor) {
+                return false;
+            }
         }
 
         self.tile_set_placed[set_key] = true;
+        self.tile_set_anchor[set_key] = Some(tile[0]);
         for coor in tile.iter() {
             self.open_coors[coor.x as usize][coor.y as usize] = false;
         }
         true
     }
 
-    pub fn remove_tile(&mut self, set_key: usize, tile: &Tile) {
+    fn remove_tile(&mut self, set_key: usize, tile: &[Coordinate]) {
         debug_assert!(self.tile_set_placed[set_key], "Tile not currently placed");
+
+        let tile: Tile = tile.iter().map(|coor| self.wrapped(coor)).collect();
         debug_assert!(
-            TileHelper::is_within_board(tile),
+            TileHelper::is_within_board(&tile),
             "Tile not within board bounds"
         );
 
         self.tile_set_placed[set_key] = false;
+        self.tile_set_anchor[set_key] = None;
         for coor in tile.iter() {
             self.open_coors[coor.x as usize][coor.y as usize] = true;
         }
     }
 
-    pub fn smallest_unplaced_tile_size(&self) -> usize {
-        self.tile_sets
-            .iter()
-            .enumerate()
-            .filter_map(|(i, tile_set)| {
-                if !self.tile_set_placed[i] {
-                    Some(tile_set[0].len())
-                } else {
-                    None
-                }
-            })
-            .min()
-            .unwrap_or(usize::MAX)
+    fn placed_piece(&self, set_key: usize, orientation: usize, tile: &[Coordinate]) -> PlacedPiece {
+        let (piece, copy) = self.tile_set_piece[set_key];
+        PlacedPiece {
+            piece,
+            copy,
+            name: self.piece_names[piece].clone(),
+            orientation: self.tile_set_orientations[set_key][orientation],
+            anchor: self.wrapped(&tile[0]),
+            tile: tile.iter().map(|coor| self.wrapped(coor)).collect(),
+        }
     }
 
-    pub fn find_islands(&self) -> Vec<Tile> {
-        let mut visited: HashSet<Coordinate> = HashSet::new();
-        let mut islands: Vec<Tile> = Vec::new();
+    fn can_skip(&self, set_key: usize) -> bool {
+        if self.mode != SolveMode::CoverBoard || self.tile_set_required[set_key] {
+            return false;
+        }
+        match self.tile_set_group[set_key] {
+            // the rest of the group still has to be able to make up the numbers
+            Some(group) => self.group_placed(group) + self.group_undecided(group) > self.group_picks[group],
+            None => true,
+        }
+    }
 
-        for x in 0..self.open_coors.len() {
-            for y in 0..self.open_coors[0].len() {
-                let coor = Coordinate {
-                    x: x as i32,
-                    y: y as i32,
-                };
-                if self.open_coors[x][y] && !visited.contains(&coor) {
-                    // found start of new island
-                    let mut island: Tile = Vec::new();
-                    let mut stack: Vec<Coordinate> = vec![coor];
+    fn skip_tile_set(&mut self, set_key: usize) {
+        debug_assert!(!self.tile_set_placed[set_key], "Tile already placed");
+        self.tile_set_skipped[set_key] = true;
+    }
 
-                    while let Some(current) = stack.pop() {
-                        if visited.contains(&current) {
-                            continue;
-                        }
-                        visited.insert(current);
-                        island.push(current);
+    fn unskip_tile_set(&mut self, set_key: usize) {
+        debug_assert!(self.tile_set_skipped[set_key], "Tile not currently skipped");
+        self.tile_set_skipped[set_key] = false;
+    }
 
-                        // check neighbors
-                        let neighbors = vec![
-                            Coordinate {
-                                x: current.x + 1,
-                                y: current.y,
-                            },
-                            Coordinate {
-                                x: current.x - 1,
-                                y: current.y,
-                            },
-                            Coordinate {
-                                x: current.x,
-                                y: current.y + 1,
-                            },
-                            Coordinate {
-                                x: current.x,
-                                y: current.y - 1,
-                            },
-                        ];
-                        for neighbor in neighbors {
-                            if neighbor.x >= 0
-                                && neighbor.x < self.open_coors.len() as i32
-                                && neighbor.y >= 0
-                                && neighbor.y < self.open_coors[0].len() as i32
-                                && self.open_coors[neighbor.x as usize][neighbor.y as usize]
-                                && !visited.contains(&neighbor)
-                            {
-                                stack.push(neighbor);
-                            }
-                        }
+    fn placements(&mut self) -> Vec<Placement> {
+        let open = self.open_coors();
+        let mut out: Vec<Placement> = Vec::new();
+        for set_key in 0..self.tile_sets.len() {
+            if self.copy_of_previous[set_key] {
+                continue;
+            }
+            let copies = 1 + self.copy_of_previous[set_key + 1..].iter().take_while(|copy| **copy).count();
+            for (orientation, tile) in self.tile_sets[set_key].clone().into_iter().enumerate() {
+                for coor in open.iter() {
+                    let mut moved = tile.clone();
+                    if !self.move_tile(&mut moved, coor) || !self.place_tile(set_key, &moved) {
+                        continue;
                     }
-
-                    islands.push(island);
+                    self.remove_tile(set_key, &moved);
+                    out.push(Placement {
+                        set_key,
+                        copies,
+                        orientation,
+                        cells: moved.iter().map(|coor| self.wrapped(coor)).collect(),
+                        tile: moved,
+                    });
                 }
             }
         }
+        out
+    }
+}
 
-        islands
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{AllTime, c};
+    use rand::SeedableRng;
+
+    // a single row (or column) of cells
+    struct Strip {
+        cells: Vec<Coordinate>,
+        wrap: Wrap,
+    }
+
+    impl CustomBoard for Strip {
+        fn name(&self) -> String {
+            "Strip".to_string()
+        }
+
+        fn tiles(&self) -> Vec<Tile> {
+            vec![vec![c(0, 0), c(1, 0)], vec![c(0, 0), c(1, 0)]]
+        }
+
+        fn coors(&self) -> Vec<Coordinate> {
+            self.cells.clone()
+        }
+
+        fn wrap(&self) -> Wrap {
+            self.wrap
+        }
+
+        fn point_in_time(&self, _pit: &AllTime) -> Option<HashSet<Coordinate>> {
+            None
+        }
+    }
+
+    fn island_sizes(cells: Vec<Coordinate>, wrap: Wrap, avoid: Coordinate) -> Vec<usize> {
+        let board = Strip { cells, wrap };
+        let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+        let active_board = ActiveBoard::from_custom(&board, &HashSet::from([avoid]), &mut rng);
+        let mut sizes: Vec<usize> = active_board.find_islands().iter().map(|island| island.len()).collect();
+        sizes.sort();
+        sizes
+    }
+
+    // with the middle cell taken, the ends of the strip only meet across the seam
+    #[test]
+    fn neighbours_reach_across_the_seam() {
+        let row: Vec<Coordinate> = (0..5).map(|x| c(x, 0)).collect();
+        assert_eq!(island_sizes(row.clone(), Wrap::None, c(2, 0)), vec![2, 2]);
+        assert_eq!(island_sizes(row.clone(), Wrap::Horizontal, c(2, 0)), vec![4]);
+        assert_eq!(island_sizes(row, Wrap::Vertical, c(2, 0)), vec![2, 2]);
+
+        let column: Vec<Coordinate> = (0..5).map(|y| c(0, y)).collect();
+        assert_eq!(island_sizes(column.clone(), Wrap::Horizontal, c(0, 2)), vec![2, 2]);
+        assert_eq!(island_sizes(column, Wrap::Both, c(0, 2)), vec![4]);
+    }
+
+    // a domino can lie across the seam, joining the last cell to the first
+    #[test]
+    fn pieces_can_be_placed_across_the_seam() {
+        let placements = |wrap: Wrap| -> Vec<Vec<Coordinate>> {
+            let board = Strip {
+                cells: (0..4).map(|x| c(x, 0)).collect(),
+                wrap,
+            };
+            let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+            let mut active_board = ActiveBoard::from_custom(&board, &HashSet::new(), &mut rng);
+            let mut cells: Vec<Vec<Coordinate>> = active_board
+                .placements()
+                .into_iter()
+                .filter(|placement| placement.set_key == 0)
+                .map(|placement| TileHelper::normalized(&placement.cells))
+                .collect();
+            cells.sort_by_key(|cells| cells.iter().map(|coor| (coor.y, coor.x)).collect::<Vec<_>>());
+            cells.dedup();
+            cells
+        };
+        assert!(!placements(Wrap::None).contains(&vec![c(0, 0), c(3, 0)]));
+        assert!(placements(Wrap::Horizontal).contains(&vec![c(0, 0), c(3, 0)]));
     }
 }
@@ -0,0 +1,251 @@
+use crate::{
+    active_board::{ActiveTileSet, Packing, Placement},
+    common::SolveMode,
+    polycube::{Coordinate3, Cubes, PlacedCubes, Polycube, PolycubePuzzle, c3},
+};
+
+// the 3d counterpart of ActiveBoard: every piece has to go in, and every cell has to be filled
+pub struct ActiveBox {
+    piece_sets: Vec<Vec<Cubes>>,          // all allowed orientations of each piece (one set per copy)
+    piece_set_piece: Vec<(usize, usize)>, // index into PolycubePuzzle::pieces(), and which copy of it
+    piece_set_placed: Vec<bool>,
+    piece_set_anchor: Vec<Option<Coordinate3>>, // where cubes[0] of the placed piece ended up
+    copy_of_previous: Vec<bool>, // the set is an identical copy of the one before it
+    size: Coordinate3,           // the container fits in 0..x, 0..y, 0..z
+    open_cells: Vec<bool>,       // indexed by ActiveBox::index
+}
+
+impl ActiveBox {
+    pub fn from_puzzle(puzzle: &dyn PolycubePuzzle) -> ActiveBox {
+        let cells = puzzle.cells();
+        assert!(
+            cells.iter().all(|coor| coor.x >= 0 && coor.y >= 0 && coor.z >= 0),
+            "Container cells must not be negative"
+        );
+        let size = c3(
+            cells.iter().map(|coor| coor.x + 1).max().unwrap_or(0),
+            cells.iter().map(|coor| coor.y + 1).max().unwrap_or(0),
+            cells.iter().map(|coor| coor.z + 1).max().unwrap_or(0),
+        );
+
+        let mut piece_sets: Vec<Vec<Cubes>> = Vec::new();
+        let mut piece_set_piece: Vec<(usize, usize)> = Vec::new();
+        let mut copy_of_previous: Vec<bool> = Vec::new();
+        for (i, piece) in puzzle.pieces().iter().enumerate() {
+            let orientations = Polycube::orientations(&piece.cubes, piece.symmetry);
+            for copy in 0..piece.count {
+                piece_sets.push(orientations.clone());
+                piece_set_piece.push((i, copy));
+                copy_of_previous.push(copy > 0);
+            }
+        }
+
+        let mut active_box = ActiveBox {
+            piece_set_placed: vec![false; piece_sets.len()],
+            piece_set_anchor: vec![None; piece_sets.len()],
+            piece_sets,
+            piece_set_piece,
+            copy_of_previous,
+            size,
+            open_cells: vec![false; (size.x * size.y * size.z) as usize],
+        };
+        for coor in cells.iter() {
+            let index = active_box.index(coor);
+            active_box.open_cells[index] = true;
+        }
+        active_box
+    }
+
+    pub fn open_cell_count(&self) -> usize {
+        self.open_cells.iter().filter(|open| **open).count()
+    }
+
+    // cells are scanned layer by layer from the bottom, then by row, then by column
+    fn index(&self, coor: &Coordinate3) -> usize {
+        ((coor.z * self.size.y + coor.y) * self.size.x + coor.x) as usize
+    }
+
+    fn coordinate(&self, index: usize) -> Coordinate3 {
+        let index = index as i32;
+        c3(
+            index % self.size.x,
+            (index / self.size.x) % self.size.y,
+            index / (self.size.x * self.size.y),
+        )
+    }
+
+    fn is_open(&self, coor: &Coordinate3) -> bool {
+        coor.x >= 0
+            && coor.x < self.size.x
+            && coor.y >= 0
+            && coor.y < self.size.y
+            && coor.z >= 0
+            && coor.z < self.size.z
+            && self.open_cells[self.index(coor)]
+    }
+}
+
+impl Packing for ActiveBox {
+    type Coor = Coordinate3;
+    type Placed = PlacedCubes;
+
+    fn mode(&self) -> SolveMode {
+        SolveMode::UseAllPieces
+    }
+
+    fn max_gaps(&self) -> usize {
+        0
+    }
+
+    fn is_covered(&self) -> bool {
+        !self.open_cells.contains(&true)
+    }
+
+    fn usage_satisfied(&self) -> bool {
+        self.piece_set_placed.iter().all(|placed| *placed)
+    }
+
+    fn get_next_tile_set(&self) -> Option<ActiveTileSet<Coordinate3>> {
+        let i = self.piece_set_placed.iter().position(|placed| !placed)?;
+        let start_after = if self.copy_of_previous[i] {
+            self.piece_set_anchor[i - 1]
+        } else {
+            None
+        };
+        Some(ActiveTileSet::new(i, self.piece_sets[i].clone(), start_after))
+    }
+
+    fn island_sizes(&self) -> Vec<usize> {
+        let mut visited = vec![false; self.open_cells.len()];
+        let mut sizes: Vec<usize> = Vec::new();
+
+        for start in 0..self.open_cells.len() {
+            if !self.open_cells[start] || visited[start] {
+                continue;
+            }
+            let mut size = 0;
+            let mut stack: Vec<Coordinate3> = vec![self.coordinate(start)];
+            visited[start] = true;
+            while let Some(current) = stack.pop() {
+                size += 1;
+                for neighbor in Polycube::neighbors(&current) {
+                    if self.is_open(&neighbor) && !visited[self.index(&neighbor)] {
+                        visited[self.index(&neighbor)] = true;
+                        stack.push(neighbor);
+                    }
+                }
+            }
+            sizes.push(size);
+        }
+
+        sizes
+    }
+
+    fn smallest_unplaced_tile_size(&self) -> usize {
+        self.piece_sets
+            .iter()
+            .zip(self.piece_set_placed.iter())
+            .filter(|(_, placed)| !**placed)
+            .map(|(piece_set, _)| piece_set[0].len())
+            .min()
+            .unwrap_or(usize::MAX)
+    }
+
+    fn get_next_open_coor(&self, current_coor: Option<&Coordinate3>) -> Option<Coordinate3> {
+        let start = current_coor.map(|coor| self.index(coor) + 1).unwrap_or(0);
+        (start..self.open_cells.len())
+            .find(|i| self.open_cells[*i])
+            .map(|i| self.coordinate(i))
+    }
+
+    fn move_tile(&self, tile: &mut [Coordinate3], coor: &Coordinate3) -> bool {
+        let offset = c3(coor.x - tile[0].x, coor.y - tile[0].y, coor.z - tile[0].z);
+        Polycube::translate(tile, &offset);
+        true
+    }
+
+    fn can_place(&self, _set_key: usize) -> bool {
+        true
+    }
+
+    fn place_tile(&mut self, set_key: usize, tile: &[Coordinate3]) -> bool {
+        debug_assert!(!self.piece_set_placed[set_key], "Piece already placed");
+
+        if !tile.iter().all(|coor| self.is_open(coor)) {
+            return false;
+        }
+
+        self.piece_set_placed[set_key] = true;
+        self.piece_set_anchor[set_key] = Some(tile[0]);
+        for coor in tile.iter() {
+            let index = self.index(coor);
+            self.open_cells[index] = false;
+        }
+        true
+    }
+
+    fn remove_tile(&mut self, set_key: usize, tile: &[Coordinate3]) {
+        debug_assert!(self.piece_set_placed[set_key], "Piece not currently placed");
+
+        self.piece_set_placed[set_key] = false;
+        self.piece_set_anchor[set_key] = None;
+        for coor in tile.iter() {
+            let index = self.index(coor);
+            self.open_cells[index] = true;
+        }
+    }
+
+    fn placed_piece(&self, set_key: usize, orientation: usize, tile: &[Coordinate3]) -> PlacedCubes {
+        let (piece, copy) = self.piece_set_piece[set_key];
+        PlacedCubes {
+            piece,
+            copy,
+            orientation,
+            anchor: tile[0],
+            cubes: tile.to_vec(),
+        }
+    }
+
+    // every piece has to go in
+    fn can_skip(&self, _set_key: usize) -> bool {
+        false
+    }
+
+    fn skip_tile_set(&mut self, _set_key: usize) {
+        unreachable!("pieces are never skipped in a box")
+    }
+
+    fn unskip_tile_set(&mut self, _set_key: usize) {
+        unreachable!("pieces are never skipped in a box")
+    }
+
+    fn placements(&mut self) -> Vec<Placement<Coordinate3>> {
+        let open: Vec<Coordinate3> = (0..self.open_cells.len())
+            .filter(|i| self.open_cells[*i])
+            .map(|i| self.coordinate(i))
+            .collect();
+        let mut out: Vec<Placement<Coordinate3>> = Vec::new();
+        for set_key in 0..self.piece_sets.len() {
+            if self.copy_of_previous[set_key] {
+                continue;
+            }
+            let copies = 1 + self.copy_of_previous[set_key + 1..].iter().take_while(|copy| **copy).count();
+            for (orientation, cubes) in self.piece_sets[set_key].iter().enumerate() {
+                for coor in open.iter() {
+                    let mut moved = cubes.clone();
+                    self.move_tile(&mut moved, coor);
+                    if moved.iter().all(|cube| self.is_open(cube)) {
+                        out.push(Placement {
+                            set_key,
+                            copies,
+                            orientation,
+                            cells: moved.clone(),
+                            tile: moved,
+                        });
+                    }
+                }
+            }
+        }
+        out
+    }
+}
@@ -0,0 +1,613 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+use crate::common::{AllTime, Coordinate, CustomBoard, Month, Piece, PieceGroup, Tile, TimeField, Usage, WeekDay, Wrap};
+use crate::lattice::Lattice;
+use crate::polyomino::Polyomino;
+
+// a board described in a text file, so a new board can be tried out without touching the code:
+//
+//   # lines starting with a '#' are comments (except under pieces:, where they are ascii art)
+//   name: Tiny Board
+//   lattice: square        (optional: square, hexagonal or triangular)
+//   wrap: none             (optional: none, horizontal, vertical or both; not for hexagonal boards)
+//   fields: month, day     (optional: which parts of the date the labels show, see FIELDS; none for a
+//                          board that shows no dates, like the message board)
+//   board:
+//   Jan Feb Mar
+//   1   2   3   *
+//   pieces:
+//   ##/#.
+//   L-tetromino
+//   0,0 1,0
+//   optional: domino
+//   pick 2: P-pentomino; U-pentomino; V-pentomino
+//
+// on the board, every word is one cell: its label, '*' for a cell without one, or '.' for a hole.
+// pieces are polyomino names, ascii art or coordinates, the same as for --add. Every piece has to be
+// used, unless it's marked optional (it can be left out), or it's one of a pick line's pieces, split
+// by ';', of which exactly that many are used. Either one means the board is solved once every cell
+// but the target is covered (see SolveMode::CoverBoard)
+pub struct FileBoard {
+    name: String,
+    lattice: Lattice,
+    wrap: Wrap,
+    fields: Vec<TimeField>,
+    cells: Vec<(Coordinate, Option<String>)>,
+    pieces: Vec<Piece>,
+    groups: Vec<PieceGroup>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BoardFileError {
+    Read(String),          // the file couldn't be read
+    Line(usize, String),   // a problem on a line (counting from 1)
+    Missing(&'static str), // a section the file has to have
+}
+
+impl std::fmt::Display for BoardFileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BoardFileError::Read(reason) => write!(f, "couldn't read the board file: {}", reason),
+            BoardFileError::Line(line, reason) => write!(f, "board file, line {}: {}", line, reason),
+            BoardFileError::Missing(section) => write!(f, "board file has no {}", section),
+        }
+    }
+}
+
+impl std::error::Error for BoardFileError {}
+
+// the names of the date fields on a fields line
+const FIELDS: [(&str, TimeField); 11] = [
+    ("month", TimeField::Month),
+    ("day", TimeField::DayOfMonth),
+    ("weekday", TimeField::WeekDay),
+    ("year", TimeField::Year),
+    ("year-last-two", TimeField::YearLastTwoDigits),
+    ("year-last-digit", TimeField::YearLastDigit),
+    ("iso-week", TimeField::IsoWeek),
+    ("day-of-year", TimeField::DayOfYear),
+    ("hour", TimeField::Hour),
+    ("minute", TimeField::Minute),
+    ("season", TimeField::Season),
+];
+
+enum Section {
+    Header,
+    Board,
+    Pieces,
+}
+
+impl FileBoard {
+    pub fn load(path: &str) -> Result<FileBoard, BoardFileError> {
+        let text = std::fs::read_to_string(path).map_err(|e| BoardFileError::Read(e.to_string()))?;
+        Self::parse(&text)
+    }
+
+    pub fn parse(text: &str) -> Result<FileBoard, BoardFileError> {
+        let mut name: Option<String> = None;
+        let mut lattice = Lattice::Square;
+        let mut wrap = Wrap::None;
+        let mut wrap_line = 0;
+        let mut fields: Option<Vec<TimeField>> = None;
+        let mut rows: Vec<(usize, &str)> = Vec::new();
+        let mut pieces: Vec<(usize, &str)> = Vec::new();
+
+        let mut section = Section::Header;
+        for (i, line) in text.lines().enumerate() {
+            let number = i + 1;
+            let trimmed = line.trim();
+            if trimmed.starts_with('#') && !matches!(section, Section::Pieces) {
+                continue;
+            }
+            match trimmed.to_lowercase().as_str() {
+                "board:" => {
+                    section = Section::Board;
+                    continue;
+                }
+                "pieces:" => {
+                    section = Section::Pieces;
+                    continue;
+                }
+                _ => {}
+            }
+            if trimmed.is_empty() {
+                continue;
+            }
+
+            match section {
+                Section::Header => {
+                    let (key, value) = match trimmed.split_once(':') {
+                        Some((key, value)) => (key.trim().to_lowercase(), value.trim()),
+                        None => return Err(BoardFileError::Line(number, "expected <key>: <value>".to_string())),
+                    };
+                    let invalid = |what: &str| BoardFileError::Line(number, format!("unknown {}: {}", what, value));
+                    match key.as_str() {
+                        "name" => name = Some(value.to_string()),
+                        "lattice" => lattice = Self::lattice(value).ok_or_else(|| invalid("lattice"))?,
+                        "wrap" => {
+                            wrap = Self::wrap(value).ok_or_else(|| invalid("wrap"))?;
+                            wrap_line = number;
+                        }
+                        "fields" if value.eq_ignore_ascii_case("none") => fields = Some(vec![]),
+                        "fields" => {
+                            let parsed: Option<Vec<TimeField>> = value.split(',').map(Self::field).collect();
+                            fields = Some(parsed.ok_or_else(|| invalid("field"))?);
+                        }
+                        _ => return Err(BoardFileError::Line(number, format!("unknown key: {}", key))),
+                    }
+                }
+                Section::Board => rows.push((number, line)),
+                Section::Pieces => pieces.push((number, trimmed)),
+            }
+        }
+
+        let name = name.ok_or(BoardFileError::Missing("name"))?;
+        if rows.is_empty() {
+            return Err(BoardFileError::Missing("board"));
+        }
+        if pieces.is_empty() {
+            return Err(BoardFileError::Missing("pieces"));
+        }
+
+        // the solver works on a 16x16 grid
+        let mut cells: Vec<(Coordinate, Option<String>)> = Vec::new();
+        for (y, (number, row)) in rows.iter().enumerate() {
+            for (x, word) in row.split_whitespace().enumerate() {
+                if x >= 16 || y >= 16 {
+                    return Err(BoardFileError::Line(*number, "boards can be at most 16×16 cells".to_string()));
+                }
+                let coor = Coordinate {
+                    x: x as i32,
+                    y: y as i32,
+                };
+                match word {
+                    "." => {}
+                    "*" => cells.push((coor, None)),
+                    label => cells.push((coor, Some(label.to_string()))),
+                }
+            }
+        }
+
+        // the board wraps at the edges of its bounding rectangle, which only lines up with the cells on the
+        // other side when the lattice's rows are straight and (for triangles) every row and column keeps
+        // alternating up and down across the edge
+        let size = |axis: fn(&Coordinate) -> i32| -> i32 {
+            let values = cells.iter().map(|(coor, _)| axis(coor));
+            values.clone().max().unwrap_or(0) - values.min().unwrap_or(0) + 1
+        };
+        let wrap_error = match lattice {
+            Lattice::Hexagonal if wrap != Wrap::None => Some("hexagonal boards can't wrap".to_string()),
+            Lattice::Triangular if wrap.horizontal() && size(|coor| coor.x) % 2 == 1 => {
+                Some("a triangular board has to be an even number of cells wide to wrap horizontally".to_string())
+            }
+            Lattice::Triangular if wrap.vertical() && size(|coor| coor.y) % 2 == 1 => {
+                Some("a triangular board has to be an even number of rows high to wrap vertically".to_string())
+            }
+            _ => None,
+        };
+        if let Some(reason) = wrap_error {
+            return Err(BoardFileError::Line(wrap_line, reason));
+        }
+
+        // ascii art, names and coordinates never have a ':' in them, so a line with one is a keyword
+        let parse = |number: usize, piece: &str| -> Result<Tile, BoardFileError> {
+            Polyomino::parse_piece(piece.trim(), lattice)
+                .ok_or_else(|| BoardFileError::Line(number, format!("invalid piece: {}", piece.trim())))
+        };
+        let mut board_pieces: Vec<Piece> = Vec::new();
+        let mut groups: Vec<PieceGroup> = Vec::new();
+        for (number, line) in pieces {
+            let (key, value) = match line.split_once(':') {
+                Some((key, value)) => (key.trim().to_lowercase(), value),
+                None => {
+                    board_pieces.push(Piece::new(parse(number, line)?));
+                    continue;
+                }
+            };
+            if key == "optional" {
+                board_pieces.push(Piece::new(parse(number, value)?).with_usage(Usage::Optional));
+                continue;
+            }
+            let pick = match key.strip_prefix("pick") {
+                Some(pick) => pick.trim().parse::<usize>().ok(),
+                None => return Err(BoardFileError::Line(number, format!("unknown key: {}", key))),
+            };
+            let first = board_pieces.len();
+            for piece in value.split(';') {
+                board_pieces.push(Piece::new(parse(number, piece)?));
+            }
+            match pick {
+                Some(pick) if pick > 0 && pick <= board_pieces.len() - first => groups.push(PieceGroup {
+                    pieces: (first..board_pieces.len()).collect(),
+                    pick,
+                }),
+                _ => {
+                    return Err(BoardFileError::Line(
+                        number,
+                        format!("expected pick <count>: with 1 to {} pieces", board_pieces.len() - first),
+                    ));
+                }
+            }
+        }
+
+        let fields = fields.unwrap_or_else(|| Self::default_fields(&cells));
+        Ok(FileBoard {
+            name,
+            lattice,
+            wrap,
+            fields,
+            cells,
+            pieces: board_pieces,
+            groups,
+        })
+    }
+
+    // any board as a board file, read back to make sure it's the same board: one that works out its date
+    // cells in a way a fields line can't say (eg. the digits board), or has pieces a board file can't
+    // describe (copies, or limited symmetry), is refused with the reason
+    pub fn write(board: &dyn CustomBoard) -> Result<String, String> {
+        let text = Self::text(board);
+        let written = Self::parse(&text).map_err(|e| format!("can't write {} as a board file: {}", board.name(), e))?;
+        match Self::difference(board, &written) {
+            Some(what) => Err(format!("a board file can't describe the {} of {}", what, board.name())),
+            None => Ok(text),
+        }
+    }
+
+    fn text(board: &dyn CustomBoard) -> String {
+        let mut text = String::new();
+        let _ = writeln!(text, "name: {}", board.name());
+        let _ = writeln!(text, "lattice: {}", format!("{:?}", board.lattice()).to_lowercase());
+        if board.wrap() != Wrap::None {
+            let _ = writeln!(text, "wrap: {}", format!("{:?}", board.wrap()).to_lowercase());
+        }
+        let fields: Vec<&str> = board
+            .time_fields()
+            .iter()
+            .filter_map(|field| FIELDS.iter().find(|(_, known)| known == field).map(|(name, _)| *name))
+            .collect();
+        if !fields.is_empty() {
+            let _ = writeln!(text, "fields: {}", fields.join(", "));
+        } else if Self::sample_days().iter().all(|day| board.point_in_time_alternatives(day).is_empty()) {
+            let _ = writeln!(text, "fields: none");
+        }
+
+        let labels = board.labels();
+        let coors = board.coors();
+        let width = coors.iter().map(|coor| coor.x + 1).max().unwrap_or(0);
+        let height = coors.iter().map(|coor| coor.y + 1).max().unwrap_or(0);
+        // every column as wide as the longest label, and a space more, so no two words run together
+        let column = labels.values().map(|label| label.chars().count()).max().unwrap_or(1).max(3) + 1;
+        let _ = writeln!(text, "board:");
+        for y in 0..height {
+            let row: Vec<String> = (0..width)
+                .map(|x| {
+                    let coor = Coordinate { x, y };
+                    let word = match labels.get(&coor) {
+                        Some(label) => label.as_str(),
+                        None if coors.contains(&coor) => "*",
+                        None => ".",
+                    };
+                    format!("{:<width$}", word, width = column)
+                })
+                .collect();
+            let _ = writeln!(text, "{}", row.concat().trim_end());
+        }
+
+        // as coordinates, which every lattice reads, in the board's order; a group's pieces go on a pick
+        // line where its first one is
+        let pieces = board.pieces();
+        let groups = board.piece_groups();
+        let coordinates = |piece: &Piece| -> String {
+            let coors: Vec<String> = piece.tile.iter().map(|coor| format!("{},{}", coor.x, coor.y)).collect();
+            coors.join(" ")
+        };
+        let _ = writeln!(text, "pieces:");
+        for (i, piece) in pieces.iter().enumerate() {
+            match groups.iter().find(|group| group.pieces.contains(&i)) {
+                Some(group) if group.pieces.first() == Some(&i) => {
+                    let members: Vec<String> = group
+                        .pieces
+                        .iter()
+                        .flat_map(|i| std::iter::repeat_n(coordinates(&pieces[*i]), pieces[*i].count))
+                        .collect();
+                    let _ = writeln!(text, "pick {}: {}", group.pick, members.join("; "));
+                }
+                Some(_) => {}
+                None => {
+                    let prefix = match piece.usage {
+                        Usage::Required => "",
+                        Usage::Optional => "optional: ",
+                    };
+                    for _ in 0..piece.count {
+                        let _ = writeln!(text, "{}{}", prefix, coordinates(piece));
+                    }
+                }
+            }
+        }
+        text
+    }
+
+    // what the written board doesn't have the same as the board it was written from, if anything
+    fn difference(board: &dyn CustomBoard, written: &FileBoard) -> Option<&'static str> {
+        let sorted = |mut coors: Vec<Coordinate>| -> Vec<Coordinate> {
+            coors.sort_by_key(|coor| (coor.y, coor.x));
+            coors
+        };
+        if sorted(board.coors()) != sorted(written.coors()) {
+            return Some("cells");
+        }
+        if board.labels() != written.labels() {
+            return Some("labels");
+        }
+
+        let lattice = board.lattice();
+        let groups = board.piece_groups();
+        let piece = |i: usize, piece: &Piece, groups: &[PieceGroup]| {
+            // a group's pieces are picked whatever their usage says
+            let usage = match groups.iter().any(|group| group.pieces.contains(&i)) {
+                true => None,
+                false => Some(piece.usage),
+            };
+            (lattice.normalized(&piece.tile), piece.symmetry, piece.count, usage)
+        };
+        let pieces: Vec<_> = board.pieces().iter().enumerate().map(|(i, p)| piece(i, p, &groups)).collect();
+        let written_pieces: Vec<_> = written.pieces.iter().enumerate().map(|(i, p)| piece(i, p, &written.groups)).collect();
+        if pieces != written_pieces {
+            return Some("pieces");
+        }
+        let picks = |groups: &[PieceGroup]| -> Vec<(Vec<usize>, usize)> {
+            groups.iter().map(|group| (group.pieces.clone(), group.pick)).collect()
+        };
+        if picks(&groups) != picks(&written.groups) {
+            return Some("piece groups");
+        }
+
+        // the cells each day leaves uncovered; a cell the board doesn't have can't be uncovered anyway
+        let coors = board.coors();
+        let shown = |board: &dyn CustomBoard, day: &AllTime| -> Vec<Vec<Coordinate>> {
+            let mut alternatives: Vec<Vec<Coordinate>> = board
+                .point_in_time_alternatives(day)
+                .into_iter()
+                .map(|cells| sorted(cells.into_iter().filter(|coor| coors.contains(coor)).collect()))
+                .collect();
+            alternatives.sort_by_key(|cells| cells.iter().map(|coor| (coor.y, coor.x)).collect::<Vec<_>>());
+            alternatives
+        };
+        if Self::sample_days().iter().any(|day| shown(board, day) != shown(written, day)) {
+            return Some("date cells");
+        }
+        None
+    }
+
+    // a decade, so every last digit of the year comes up, and leap days too
+    fn sample_days() -> Vec<AllTime> {
+        (2020..2030).flat_map(AllTime::every_day_of).collect()
+    }
+
+    fn lattice(value: &str) -> Option<Lattice> {
+        match value.to_lowercase().as_str() {
+            "square" => Some(Lattice::Square),
+            "hexagonal" => Some(Lattice::Hexagonal),
+            "triangular" => Some(Lattice::Triangular),
+            _ => None,
+        }
+    }
+
+    fn wrap(value: &str) -> Option<Wrap> {
+        match value.to_lowercase().as_str() {
+            "none" => Some(Wrap::None),
+            "horizontal" => Some(Wrap::Horizontal),
+            "vertical" => Some(Wrap::Vertical),
+            "both" => Some(Wrap::Both),
+            _ => None,
+        }
+    }
+
+    fn field(value: &str) -> Option<TimeField> {
+        let value = value.trim().to_lowercase();
+        FIELDS.iter().find(|(name, _)| *name == value).map(|(_, field)| *field)
+    }
+
+    // without a fields line: the month, day and weekday, for whichever of them have labels on the board
+    fn default_fields(cells: &[(Coordinate, Option<String>)]) -> Vec<TimeField> {
+        let labels: Vec<&str> = cells.iter().filter_map(|(_, label)| label.as_deref()).collect();
+        let has = |matches: &dyn Fn(&str) -> bool| labels.iter().any(|label| matches(label));
+
+        let mut fields: Vec<TimeField> = Vec::new();
+        if has(&|label| (1..=12).filter_map(Month::from_u32).any(|m| m.abbreviation().eq_ignore_ascii_case(label))) {
+            fields.push(TimeField::Month);
+        }
+        if has(&|label| label.parse::<u32>().is_ok_and(|day| (1..=31).contains(&day))) {
+            fields.push(TimeField::DayOfMonth);
+        }
+        if has(&|label| (0..7).filter_map(WeekDay::from_u32).any(|d| d.abbreviation().eq_ignore_ascii_case(label))) {
+            fields.push(TimeField::WeekDay);
+        }
+        fields
+    }
+}
+
+impl CustomBoard for FileBoard {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn tiles(&self) -> Vec<Tile> {
+        self.pieces.iter().map(|piece| piece.tile.clone()).collect()
+    }
+
+    fn pieces(&self) -> Vec<Piece> {
+        self.pieces.clone()
+    }
+
+    fn piece_groups(&self) -> Vec<PieceGroup> {
+        self.groups.clone()
+    }
+
+    fn coors(&self) -> Vec<Coordinate> {
+        self.cells.iter().map(|(coor, _)| *coor).collect()
+    }
+
+    fn lattice(&self) -> Lattice {
+        self.lattice
+    }
+
+    fn wrap(&self) -> Wrap {
+        self.wrap
+    }
+
+    // a board without any date fields can't show a date at all
+    fn point_in_time(&self, pit: &AllTime) -> Option<HashSet<Coordinate>> {
+        if self.fields.is_empty() {
+            return None;
+        }
+        self.point_in_time_alternatives(pit).into_iter().next()
+    }
+
+    fn labels(&self) -> HashMap<Coordinate, String> {
+        self.cells
+            .iter()
+            .filter_map(|(coor, label)| Some((*coor, label.clone()?)))
+            .collect()
+    }
+
+    fn time_fields(&self) -> Vec<TimeField> {
+        self.fields.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::Target;
+    use crate::custom_boards::{CUSTOM_BOARDS, MESSAGE_BOARDS};
+    use crate::solver::{Backend, Search};
+
+    fn sorted(mut coors: Vec<Coordinate>) -> Vec<Coordinate> {
+        coors.sort_by_key(|coor| (coor.y, coor.x));
+        coors
+    }
+
+    fn count(board: &dyn CustomBoard, target: &Target) -> usize {
+        Search::new().with_backend(Backend::ExactCover).count(target, board).unwrap()
+    }
+
+    // every board either loads back as itself, with the same solutions, or is refused outright
+    #[test]
+    fn written_boards_load_back_the_same() {
+        let date = Target::Date(AllTime::from_iso("2026-03-05").unwrap());
+        let message = Target::Labels(["G", "A", "M", "E"].iter().map(|label| label.to_string()).collect());
+        let boards = CUSTOM_BOARDS.iter().map(|board| (*board, &date));
+        let boards = boards.chain(MESSAGE_BOARDS.iter().map(|board| (*board, &message)));
+
+        let mut refused: Vec<String> = Vec::new();
+        for (board, target) in boards {
+            let text = match FileBoard::write(board) {
+                Ok(text) => text,
+                Err(_) => {
+                    refused.push(board.name());
+                    continue;
+                }
+            };
+            let written = FileBoard::parse(&text).unwrap();
+            assert_eq!(written.name(), board.name());
+            assert_eq!(sorted(written.coors()), sorted(board.coors()), "{}", board.name());
+            assert_eq!(written.labels(), board.labels(), "{}", board.name());
+            let pieces = |board: &dyn CustomBoard| -> Vec<(Tile, usize)> {
+                let lattice = board.lattice();
+                board.pieces().iter().map(|piece| (lattice.normalized(&piece.tile), piece.count)).collect()
+            };
+            assert_eq!(pieces(&written), pieces(board), "{}", board.name());
+            assert_eq!(count(&written, target), count(board, target), "{}", board.name());
+        }
+
+        // the digits board works its cells out from the digits' shapes, and the tetromino board shows
+        // thursday to saturday off the board
+        assert_eq!(refused, vec!["Generic 'Tetromino' Board".to_string(), "Digits Calendar Board".to_string()]);
+    }
+
+    fn error(text: &str) -> String {
+        FileBoard::parse(text).err().expect("the board file should be rejected").to_string()
+    }
+
+    #[test]
+    fn unknown_keys_are_rejected() {
+        assert_eq!(
+            error("name: Tiny\ncolour: red\nboard:\n* *\npieces:\ndomino"),
+            "board file, line 2: unknown key: colour"
+        );
+        assert_eq!(
+            error("name: Tiny\nboard:\n* *\npieces:\ntwice: domino"),
+            "board file, line 5: unknown key: twice"
+        );
+        assert_eq!(
+            error("name: Tiny\nlattice: octagonal\nboard:\n* *\npieces:\ndomino"),
+            "board file, line 2: unknown lattice: octagonal"
+        );
+        assert_eq!(
+            error("name: Tiny\nfields: month, fortnight\nboard:\n* *\npieces:\ndomino"),
+            "board file, line 2: unknown field: month, fortnight"
+        );
+        assert_eq!(error("name: Tiny\nno key here\nboard:\n* *"), "board file, line 2: expected <key>: <value>");
+    }
+
+    #[test]
+    fn sections_are_required() {
+        assert_eq!(error("board:\n* *\npieces:\ndomino"), "board file has no name");
+        assert_eq!(error("name: Tiny\npieces:\ndomino"), "board file has no board");
+        assert_eq!(error("name: Tiny\nboard:\n* *"), "board file has no pieces");
+    }
+
+    #[test]
+    fn pieces_have_to_be_single_shapes() {
+        // ascii art rows don't have to be the same length, but the cells have to touch
+        let board = FileBoard::parse("name: Tiny\nboard:\n* *\n* .\npieces:\n##/#").unwrap();
+        assert_eq!(board.pieces[0].tile.len(), 3);
+        assert_eq!(
+            error("name: Tiny\nboard:\n* *\npieces:\n#./.#"),
+            "board file, line 5: invalid piece: #./.#"
+        );
+        assert_eq!(
+            error("name: Tiny\nboard:\n* *\npieces:\nmonomino\nQ-pentomino"),
+            "board file, line 6: invalid piece: Q-pentomino"
+        );
+        assert_eq!(
+            error("name: Tiny\nboard:\n* *\npieces:\npick 3: domino; monomino"),
+            "board file, line 5: expected pick <count>: with 1 to 2 pieces"
+        );
+    }
+
+    #[test]
+    fn boards_have_to_fit_the_solver() {
+        let wide = format!("name: Wide\nboard:\n{}\npieces:\ndomino", ["*"; 17].join(" "));
+        assert_eq!(error(&wide), "board file, line 3: boards can be at most 16×16 cells");
+        assert_eq!(
+            error("name: Tiny\nlattice: hexagonal\nwrap: both\nboard:\n* *\npieces:\ndomino"),
+            "board file, line 3: hexagonal boards can't wrap"
+        );
+    }
+
+    // labels can repeat (eg. a word on a message board), and the area is only checked once there's a
+    // target, so neither stops a board file from loading
+    #[test]
+    fn duplicate_labels_and_area_are_left_to_the_solver() {
+        let board = FileBoard::parse("name: Tiny\nfields: none\nboard:\nA A *\npieces:\ndomino").unwrap();
+        assert_eq!(board.labels().values().filter(|label| *label == "A").count(), 2);
+
+        let target = Target::Labels(vec!["A".to_string()]);
+        assert_eq!(board.target_alternatives(&target).len(), 2);
+        let lopsided = FileBoard::parse("name: Tiny\nfields: none\nboard:\nA * *\npieces:\nI-tromino").unwrap();
+        assert_eq!(Search::new().count(&target, &lopsided), Err(crate::solver::AREA_MISMATCH));
+    }
+
+    #[test]
+    fn refused_boards_say_why() {
+        let digits = CUSTOM_BOARDS.iter().find(|board| board.name() == "Digits Calendar Board").unwrap();
+        assert_eq!(
+            FileBoard::write(*digits).unwrap_err(),
+            "a board file can't describe the date cells of Digits Calendar Board"
+        );
+    }
+}
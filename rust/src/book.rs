@@ -0,0 +1,167 @@
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::common::{AllTime, CustomBoard, Target};
+use crate::drawing::Style;
+use crate::solver::{self, Search};
+use crate::svg::{SvgRenderer, escape};
+
+// a puzzle for every day of a year, to print as a booklet: for each date, a page with the empty board
+// (the date shaded), a hint page with some of the pieces put down, and an answer page at the back.
+// Every page is its own SVG file, and index.html puts them in order, one per printed page
+pub struct PuzzleBook<'a> {
+    board: &'a dyn CustomBoard,
+    year: i32,
+    search: Search,
+    hide: usize, // pieces left off the hint page
+    style: Style,
+}
+
+// what the book has for one day
+#[derive(Debug, Clone, Serialize)]
+pub struct BookDay {
+    pub date: String,
+    pub status: &'static str, // "written", "invalid" (the board can't show it) or "no-solution"
+    pub pages: Vec<String>,   // file names, relative to the book's directory
+}
+
+#[derive(Debug)]
+pub enum BookError {
+    Write(PathBuf, std::io::Error),
+    Solver(String, u32), // the date, and the solver's error code
+}
+
+impl std::fmt::Display for BookError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BookError::Write(path, e) => write!(f, "couldn't write {}: {}", path.display(), e),
+            BookError::Solver(date, code) => write!(f, "couldn't solve {} (error {})", date, code),
+        }
+    }
+}
+
+impl std::error::Error for BookError {}
+
+impl<'a> PuzzleBook<'a> {
+    pub fn new(board: &'a dyn CustomBoard, year: i32) -> PuzzleBook<'a> {
+        PuzzleBook {
+            board,
+            year,
+            search: Search::new(),
+            hide: 5,
+            style: Style::new(),
+        }
+    }
+
+    pub fn with_search(mut self, search: Search) -> PuzzleBook<'a> {
+        self.search = search;
+        self
+    }
+
+    pub fn with_hide(mut self, hide: usize) -> PuzzleBook<'a> {
+        self.hide = hide;
+        self
+    }
+
+    pub fn with_style(mut self, style: Style) -> PuzzleBook<'a> {
+        self.style = style;
+        self
+    }
+
+    // writes the pages and index.html into the directory (creating it if need be); dates the board
+    // can't show, or that have no solution, are left out
+    pub fn write(&self, directory: &Path) -> Result<Vec<BookDay>, BookError> {
+        let write = |name: &str, contents: &str| -> Result<(), BookError> {
+            let path = directory.join(name);
+            std::fs::write(&path, contents).map_err(|e| BookError::Write(path, e))
+        };
+        std::fs::create_dir_all(directory).map_err(|e| BookError::Write(directory.to_path_buf(), e))?;
+
+        let mut rng = self.search.rng();
+        let mut days: Vec<BookDay> = Vec::new();
+        for day in AllTime::every_day_of(self.year) {
+            let date = day.iso_date().unwrap_or_default();
+            let target = Target::Date(day.clone());
+            let solution = match self.search.run(&target, self.board) {
+                Ok(solution) => solution,
+                Err(solver::INVALID_TARGET) => {
+                    days.push(BookDay { date, status: "invalid", pages: vec![] });
+                    continue;
+                }
+                Err(solver::NO_SOLUTION_FOUND) => {
+                    days.push(BookDay { date, status: "no-solution", pages: vec![] });
+                    continue;
+                }
+                Err(e) => return Err(BookError::Solver(date, e)),
+            };
+
+            let mut shaded = solution.target.clone();
+            shaded.extend(solution.gaps.iter().copied());
+            let hidden = solver::hidden_pieces(solution.pieces.len(), self.hide, &mut rng);
+            let renderer = SvgRenderer::new(self.style.clone());
+            let hint = SvgRenderer::new(self.style.clone().with_hidden(hidden));
+            let pages = [
+                (format!("{}-puzzle.svg", date), renderer.puzzle(self.board, &shaded)),
+                (format!("{}-hint.svg", date), hint.solution(self.board, &solution)),
+                (format!("{}-answer.svg", date), renderer.solution(self.board, &solution)),
+            ];
+            for (name, svg) in pages.iter() {
+                write(name, svg)?;
+            }
+            days.push(BookDay {
+                date,
+                status: "written",
+                pages: pages.into_iter().map(|(name, _)| name).collect(),
+            });
+        }
+
+        write("index.html", &self.index(&days))?;
+        Ok(days)
+    }
+
+    // the puzzle and hint for each day, then every answer at the back, so they can't be seen by accident
+    fn index(&self, days: &[BookDay]) -> String {
+        let title = format!("{} {}", escape(&self.board.name()), self.year);
+        let mut html = String::new();
+        html += "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n";
+        html += &format!("<title>{}</title>\n", title);
+        html += "<style>\n";
+        html += "@page { size: A4; margin: 15mm; }\n";
+        html += "body { font-family: sans-serif; text-align: center; }\n";
+        html += "section { break-after: page; }\n";
+        html += "img { width: 100%; max-height: 220mm; }\n";
+        html += "</style>\n</head>\n<body>\n";
+        html += &format!("<section>\n<h1>{}</h1>\n<p>A puzzle for every day of the year</p>\n</section>\n", title);
+
+        let written: Vec<&BookDay> = days.iter().filter(|day| day.status == "written").collect();
+        for day in written.iter() {
+            let heading = heading(&day.date);
+            html += &page(&heading, &day.pages[0]);
+            html += &page(&format!("{}: hint", heading), &day.pages[1]);
+        }
+        html += "<section>\n<h1>Answers</h1>\n</section>\n";
+        for day in written.iter() {
+            html += &page(&heading(&day.date), &day.pages[2]);
+        }
+        html += "</body>\n</html>\n";
+        html
+    }
+}
+
+fn page(heading: &str, image: &str) -> String {
+    format!(
+        "<section>\n<h2>{}</h2>\n<img src=\"{}\" alt=\"{}\">\n</section>\n",
+        escape(heading),
+        escape(image),
+        escape(heading)
+    )
+}
+
+// eg. "Monday, October 19"
+fn heading(iso_date: &str) -> String {
+    match chrono::NaiveDate::parse_from_str(iso_date, "%Y-%m-%d") {
+        Ok(date) => date.format("%A, %B %-d").to_string(),
+        Err(_) => iso_date.to_string(),
+    }
+}
@@ -1,6 +1,8 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use serde::{Serialize, Deserialize};
 
+use crate::lattice::Lattice;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Coordinate {
     pub x: i32,
@@ -13,15 +15,264 @@ pub fn c(x: i32, y: i32) -> Coordinate {
 
 pub type Tile = Vec<Coordinate>;
 
+// which orientations a piece is allowed to take on the board
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Symmetry {
+    All,       // four rotations, each of them flipped or not
+    Rotations, // one-sided piece: can be rotated but never flipped over
+    Fixed,     // must be placed exactly as defined
+}
+
+// how a piece was turned before being placed: flipped horizontally first (if at all),
+// then rotated a number of times with Lattice::rotate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Orientation {
+    pub rotation: u32, // quarter turns on a square lattice, sixth turns on hexagonal / triangular ones
+    pub flipped: bool,
+}
+
+// one piece of a solution, as it sits on the board
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlacedPiece {
+    pub piece: usize, // index into CustomBoard::pieces()
+    pub copy: usize,  // which copy of the piece, when there are several
+    pub name: Option<String>, // polyomino name of the piece, eg. "Z-pentomino" (only on square lattices)
+    pub orientation: Orientation,
+    pub anchor: Coordinate, // where tile[0] of the piece ended up
+    pub tile: Tile,         // every coordinate covered by the piece
+}
+
+// a solved board
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Solution {
+    pub pieces: Vec<PlacedPiece>, // ordered by piece (and copy), the same way as CustomBoard::pieces()
+    pub target: Vec<Coordinate>,  // the cells left uncovered to show the target
+    pub encoding: usize,          // which of CustomBoard::target_alternatives() the target cells are
+    pub gaps: Vec<Coordinate>,    // any other cells left uncovered (see solver::run_leaving_gaps)
+}
+
+// does a piece have to end up on the board?
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Usage {
+    Required,
+    Optional,
+}
+
+#[derive(Debug, Clone)]
+pub struct Piece {
+    pub tile: Tile,
+    pub symmetry: Symmetry,
+    pub count: usize, // number of identical copies of this piece in the kit
+    pub usage: Usage, // ignored when the piece is part of a PieceGroup
+}
+
+// "use exactly `pick` of these pieces"; copies of a piece each count towards `pick`
+#[derive(Debug, Clone)]
+pub struct PieceGroup {
+    pub pieces: Vec<usize>, // indices into CustomBoard::pieces()
+    pub pick: usize,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolveMode {
+    UseAllPieces, // every piece must be placed (the classic puzzle)
+    CoverBoard,   // done once every open cell is covered; only required pieces have to be used
+}
+
+// which edges of the board join up with the opposite edge, so pieces can run off one side and
+// come back in on the other (like a calendar printed around a cylinder)
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Wrap {
+    None,
+    Horizontal, // the left and right edges meet (a cylinder)
+    Vertical,   // the top and bottom edges meet
+    Both,       // a torus
+}
+
+impl Wrap {
+    pub fn horizontal(&self) -> bool {
+        matches!(self, Wrap::Horizontal | Wrap::Both)
+    }
+
+    pub fn vertical(&self) -> bool {
+        matches!(self, Wrap::Vertical | Wrap::Both)
+    }
+}
+
+impl Piece {
+    pub fn new(tile: Tile) -> Piece {
+        Piece {
+            tile,
+            symmetry: Symmetry::All,
+            count: 1,
+            usage: Usage::Required,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn with_symmetry(mut self, symmetry: Symmetry) -> Piece {
+        self.symmetry = symmetry;
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn with_count(mut self, count: usize) -> Piece {
+        self.count = count;
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn with_usage(mut self, usage: Usage) -> Piece {
+        self.usage = usage;
+        self
+    }
+}
+
 pub trait CustomBoard {
     #[allow(dead_code)]
     fn name(&self) -> String;
     fn tiles(&self) -> Vec<Tile>;
+    // override to restrict how individual pieces may be oriented, or to include several copies of a piece
+    fn pieces(&self) -> Vec<Piece> {
+        self.tiles().into_iter().map(Piece::new).collect()
+    }
+    fn piece_groups(&self) -> Vec<PieceGroup> {
+        vec![]
+    }
+    // any optional piece (or group of pieces) means not every piece can be expected on the board
+    fn solve_mode(&self) -> SolveMode {
+        let any_optional = self.pieces().iter().any(|p| p.usage == Usage::Optional);
+        if any_optional || !self.piece_groups().is_empty() {
+            SolveMode::CoverBoard
+        } else {
+            SolveMode::UseAllPieces
+        }
+    }
     fn coors(&self) -> Vec<Coordinate>;
+    // the grid that coors() and the tiles are laid out on
+    fn lattice(&self) -> Lattice {
+        Lattice::Square
+    }
+    // the board wraps around at the edges of the smallest rectangle holding coors()
+    // (on a triangular lattice, a wrapping width or height has to be even; hexagonal boards can't wrap)
+    fn wrap(&self) -> Wrap {
+        Wrap::None
+    }
     fn point_in_time(&self, pit: &AllTime) -> Option<HashSet<Coordinate>>;
+    // a name for each cell that can be left uncovered, eg. "Jan", "15" or "Mon"; by default this is worked
+    // out from point_in_time: the cell every day of January leaves open is "Jan", and so on
+    fn labels(&self) -> HashMap<Coordinate, String> {
+        let days = AllTime::every_day_of(2024); // a leap year, so February 29 gets a label too
+        let open: Vec<(&AllTime, HashSet<Coordinate>)> =
+            days.iter().filter_map(|day| Some((day, self.point_in_time(day)?))).collect();
+        let common_cell = |matches: &dyn Fn(&AllTime) -> bool| -> Option<Coordinate> {
+            let mut sets = open.iter().filter(|(day, _)| matches(day)).map(|(_, coors)| coors);
+            let first = sets.next()?.clone();
+            let common: Vec<Coordinate> =
+                sets.fold(first, |acc, coors| &acc & coors).into_iter().collect();
+            match common[..] {
+                [coor] => Some(coor),
+                _ => None,
+            }
+        };
+
+        let mut out: HashMap<Coordinate, String> = HashMap::new();
+        for month in 1..=12 {
+            if let Some(coor) = common_cell(&|day| day.month().number() == month) {
+                out.insert(coor, Month::from_u32(month).unwrap().abbreviation().to_string());
+            }
+        }
+        for day_of_month in 1..=31 {
+            if let Some(coor) = common_cell(&|day| day.day_of_month() == day_of_month) {
+                out.insert(coor, day_of_month.to_string());
+            }
+        }
+        for week_day in 0..7 {
+            if let Some(coor) = common_cell(&|day| day.week_day().number() == week_day) {
+                out.insert(coor, WeekDay::from_u32(week_day).unwrap().abbreviation().to_string());
+            }
+        }
+        // point_in_time can name cells the board doesn't have (eg. a date the board can't show at all)
+        let coors = self.coors();
+        out.retain(|coor, _| coors.contains(coor));
+        out
+    }
+    // every way the board can show the date, for boards with more than one cell that can stand for
+    // the same thing (eg. two "1" cells, so the 11th can be shown at all); boards that list
+    // time_fields() get these from their labels
+    fn point_in_time_alternatives(&self, pit: &AllTime) -> Vec<HashSet<Coordinate>> {
+        let fields = self.time_fields();
+        if fields.is_empty() {
+            return self.point_in_time(pit).into_iter().collect();
+        }
+        let labels: Option<Vec<String>> = fields.into_iter().map(|field| pit.label(field)).collect();
+        match labels {
+            Some(labels) => self.target_alternatives(&Target::Labels(labels)),
+            None => vec![],
+        }
+    }
+    // the parts of the point in time the board shows, for boards that label their cells (with
+    // AllTime::label) instead of working out the cells in point_in_time
+    fn time_fields(&self) -> Vec<TimeField> {
+        vec![]
+    }
+    // every set of cells that shows the target; empty if the board can't show it at all
+    fn target_alternatives(&self, target: &Target) -> Vec<HashSet<Coordinate>> {
+        match target {
+            Target::Date(pit) => self.point_in_time_alternatives(pit),
+            Target::Coordinates(coors) => {
+                let board_coors = self.coors();
+                if coors.iter().all(|coor| board_coors.contains(coor)) {
+                    vec![coors.clone()]
+                } else {
+                    vec![]
+                }
+            }
+            Target::Labels(labels) => {
+                // a label can be on more than one cell, and every way of picking a different cell
+                // for each label shows the same thing
+                let board_labels = self.labels();
+                let mut out: Vec<HashSet<Coordinate>> = vec![HashSet::new()];
+                for label in labels {
+                    let mut cells: Vec<Coordinate> = board_labels
+                        .iter()
+                        .filter(|(_, l)| l.eq_ignore_ascii_case(label))
+                        .map(|(coor, _)| *coor)
+                        .collect();
+                    cells.sort_by_key(|coor| (coor.y, coor.x));
+
+                    let mut next: Vec<HashSet<Coordinate>> = Vec::new();
+                    for chosen in out.iter() {
+                        for cell in cells.iter().filter(|cell| !chosen.contains(cell)) {
+                            let mut alternative = chosen.clone();
+                            alternative.insert(*cell);
+                            if !next.contains(&alternative) {
+                                next.push(alternative);
+                            }
+                        }
+                    }
+                    out = next;
+                }
+                out
+            }
+        }
+    }
 }
 
-#[derive(Debug)]
+// what a board should leave uncovered once all the pieces are placed
+#[allow(dead_code)]
+#[derive(Debug, Clone)]
+pub enum Target {
+    Date(AllTime),                    // whichever cells the board uses for the date (CustomBoard::point_in_time)
+    Labels(Vec<String>),              // one cell for each label (CustomBoard::labels), eg. the letters of a word
+    Coordinates(HashSet<Coordinate>), // exactly these cells
+}
+
+#[derive(Debug, Clone)]
 pub enum Month {
     January,
     February,
@@ -37,7 +288,7 @@ pub enum Month {
     December,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum WeekDay {
     Sunday,
     Monday,
@@ -49,8 +300,8 @@ pub enum WeekDay {
 }
 
 impl Month {
-    pub fn from_datetime(value: chrono::DateTime<chrono::Local>) -> Option<Month> {
-        let numeric = chrono::Datelike::month(&value);
+    pub fn from_datetime<Tz: chrono::TimeZone>(value: &chrono::DateTime<Tz>) -> Option<Month> {
+        let numeric = chrono::Datelike::month(value);
         match numeric {
             1 => Some(Month::January),
             2 => Some(Month::February),
@@ -86,11 +337,32 @@ impl Month {
             _ => None,
         }
     }
+
+    pub fn number(&self) -> u32 {
+        self.clone() as u32 + 1
+    }
+
+    pub fn abbreviation(&self) -> &'static str {
+        match self {
+            Month::January => "Jan",
+            Month::February => "Feb",
+            Month::March => "Mar",
+            Month::April => "Apr",
+            Month::May => "May",
+            Month::June => "Jun",
+            Month::July => "Jul",
+            Month::August => "Aug",
+            Month::September => "Sep",
+            Month::October => "Oct",
+            Month::November => "Nov",
+            Month::December => "Dec",
+        }
+    }
 }
 
 impl WeekDay {
-    pub fn from_datetime(value: chrono::DateTime<chrono::Local>) -> Option<WeekDay> {
-        let numeric = chrono::Datelike::weekday(&value).num_days_from_sunday();
+    pub fn from_datetime<Tz: chrono::TimeZone>(value: &chrono::DateTime<Tz>) -> Option<WeekDay> {
+        let numeric = chrono::Datelike::weekday(value).num_days_from_sunday();
         Self::from_u32(numeric)
     }
 
@@ -106,37 +378,257 @@ impl WeekDay {
             _ => None,
         }
     }
+
+    // days from Sunday, the same as from_u32
+    pub fn number(&self) -> u32 {
+        self.clone() as u32
+    }
+
+    pub fn abbreviation(&self) -> &'static str {
+        match self {
+            WeekDay::Sunday => "Sun",
+            WeekDay::Monday => "Mon",
+            WeekDay::Tuesday => "Tue",
+            WeekDay::Wednesday => "Wed",
+            WeekDay::Thursday => "Thu",
+            WeekDay::Friday => "Fri",
+            WeekDay::Saturday => "Sat",
+        }
+    }
 }
 
 pub type DayOfMonth = u32;
 
-#[derive(Debug)]
+// meteorological seasons in the northern hemisphere: whole months, starting with March, June,
+// September and December
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Season {
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+impl Season {
+    pub fn from_month(month: &Month) -> Season {
+        match month {
+            Month::March | Month::April | Month::May => Season::Spring,
+            Month::June | Month::July | Month::August => Season::Summer,
+            Month::September | Month::October | Month::November => Season::Autumn,
+            Month::December | Month::January | Month::February => Season::Winter,
+        }
+    }
+}
+
+// the parts of a point in time a board can show; see AllTime::label for how each one is written
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeField {
+    Month,             // "Jan"
+    DayOfMonth,        // "1" to "31"
+    WeekDay,           // "Sun"
+    Year,              // "2026"
+    YearLastTwoDigits, // "'26"
+    YearLastDigit,     // "'6"
+    IsoWeek,           // "W01" to "W53"
+    DayOfYear,         // "D1" to "D366"
+    Hour,              // "0h" to "23h"
+    Minute,            // "00m" to "59m"
+    Season,            // "Spring"
+}
+
+// why a date couldn't be turned into an AllTime
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DateError {
+    Format(String),                                // not of the form YYYY-MM-DD
+    NoSuchDay { year: i32, month: u32, day: u32 }, // eg. February 30th, or month 13
+}
+
+impl std::fmt::Display for DateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DateError::Format(value) => write!(f, "\"{}\" is not a date, expected YYYY-MM-DD", value),
+            DateError::NoSuchDay { year, month, day } => {
+                write!(f, "{:04}-{:02}-{:02} is not a day in the calendar", year, month, day)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DateError {}
+
+// which clock "today" is read from
+#[derive(Debug, Clone)]
+pub enum Zone {
+    Utc,
+    Local,                      // whatever the machine is set to
+    Fixed(chrono::FixedOffset), // eg. "+05:30"
+    #[cfg(feature = "tz")]
+    Named(chrono_tz::Tz), // an IANA name, eg. "America/Halifax"
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ZoneError {
+    Unknown(String),        // not "utc", "local", an offset or (with the tz feature) an IANA name
+    #[allow(dead_code)]
+    NeedsTzFeature(String), // looks like an IANA name, but the tz feature is off
+    Clock,                  // the current time couldn't be read as a date
+}
+
+impl Zone {
+    // "utc", "local", an offset from UTC ("+05:30", "-0800", "Z"), or an IANA name ("Europe/Paris") with the tz feature
+    pub fn parse(value: &str) -> Result<Zone, ZoneError> {
+        let value = value.trim();
+        match value.to_lowercase().as_str() {
+            "utc" | "z" => return Ok(Zone::Utc),
+            "local" => return Ok(Zone::Local),
+            _ => {}
+        }
+        if value.starts_with('+') || value.starts_with('-') {
+            return Self::parse_offset(value).map(Zone::Fixed).ok_or(ZoneError::Unknown(value.to_string()));
+        }
+        Self::parse_named(value)
+    }
+
+    // [+-]HH, [+-]HHMM or [+-]HH:MM
+    fn parse_offset(value: &str) -> Option<chrono::FixedOffset> {
+        let sign = if value.starts_with('-') { -1 } else { 1 };
+        let digits = value[1..].replace(':', "");
+        if !digits.chars().all(|ch| ch.is_ascii_digit()) {
+            return None;
+        }
+        let (hours, minutes) = match digits.len() {
+            2 => (digits.parse::<i32>().ok()?, 0),
+            4 => (digits[..2].parse::<i32>().ok()?, digits[2..].parse::<i32>().ok()?),
+            _ => return None,
+        };
+        if minutes >= 60 {
+            return None;
+        }
+        chrono::FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+    }
+
+    #[cfg(feature = "tz")]
+    fn parse_named(value: &str) -> Result<Zone, ZoneError> {
+        value
+            .parse::<chrono_tz::Tz>()
+            .map(Zone::Named)
+            .map_err(|_| ZoneError::Unknown(value.to_string()))
+    }
+
+    #[cfg(not(feature = "tz"))]
+    fn parse_named(value: &str) -> Result<Zone, ZoneError> {
+        if value.contains('/') {
+            Err(ZoneError::NeedsTzFeature(value.to_string()))
+        } else {
+            Err(ZoneError::Unknown(value.to_string()))
+        }
+    }
+}
+
+impl std::fmt::Display for ZoneError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ZoneError::Unknown(value) => write!(f, "unknown time zone \"{}\"", value),
+            ZoneError::NeedsTzFeature(value) => {
+                write!(f, "time zone names like \"{}\" need the tz feature (cargo build --features tz)", value)
+            }
+            ZoneError::Clock => write!(f, "couldn't read today's date from the clock"),
+        }
+    }
+}
+
+impl std::error::Error for ZoneError {}
+
+#[derive(Debug, Clone)]
 pub struct AllTime {
     month: Month,
     day_of_month: DayOfMonth,
     #[allow(dead_code)]
     week_day: WeekDay,
+    year: Option<i32>,        // needed for the year, ISO week and day of year
+    time: Option<(u32, u32)>, // hour and minute
 }
 
 impl AllTime {
+    // nothing is checked here (eg. February 31st, or a weekday that doesn't match); prefer from_ymd or from_iso
     #[allow(dead_code)]
     pub fn new(month: Month, day_of_month: DayOfMonth, week_day: WeekDay) -> AllTime {
         AllTime {
             month,
             day_of_month,
             week_day,
+            year: None,
+            time: None,
         }
     }
 
-    pub fn from_datetime(value: chrono::DateTime<chrono::Local>) -> Option<AllTime> {
+    #[allow(dead_code)]
+    pub fn with_year(mut self, year: i32) -> AllTime {
+        self.year = Some(year);
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn with_time(mut self, hour: u32, minute: u32) -> AllTime {
+        self.time = Some((hour, minute));
+        self
+    }
+
+    // the date and time as seen on a clock in the datetime's own time zone
+    pub fn from_datetime<Tz: chrono::TimeZone>(value: &chrono::DateTime<Tz>) -> Option<AllTime> {
         let month = Month::from_datetime(value)?;
-        let day_of_month = chrono::Datelike::day(&value);
+        let day_of_month = chrono::Datelike::day(value);
         let week_day = WeekDay::from_datetime(value)?;
-        Some(AllTime {
-            month,
-            day_of_month,
-            week_day,
-        })
+        Some(
+            AllTime::new(month, day_of_month, week_day)
+                .with_year(chrono::Datelike::year(value))
+                .with_time(chrono::Timelike::hour(value), chrono::Timelike::minute(value)),
+        )
+    }
+
+    // the weekday comes from the date, so the fields always agree
+    pub fn from_date(date: chrono::NaiveDate) -> AllTime {
+        let month = Month::from_u32(chrono::Datelike::month(&date)).expect("chrono months are 1 to 12");
+        let week_day = WeekDay::from_u32(chrono::Datelike::weekday(&date).num_days_from_sunday())
+            .expect("chrono weekdays are 0 to 6 from Sunday");
+        AllTime::new(month, chrono::Datelike::day(&date), week_day).with_year(chrono::Datelike::year(&date))
+    }
+
+    pub fn from_ymd(year: i32, month: u32, day: u32) -> Result<AllTime, DateError> {
+        match chrono::NaiveDate::from_ymd_opt(year, month, day) {
+            Some(date) => Ok(AllTime::from_date(date)),
+            None => Err(DateError::NoSuchDay { year, month, day }),
+        }
+    }
+
+    // eg. "2026-02-28"
+    pub fn from_iso(value: &str) -> Result<AllTime, DateError> {
+        let format_error = || DateError::Format(value.to_string());
+        let parts: Vec<&str> = value.trim().split('-').collect();
+        let (year, month, day) = match parts.as_slice() {
+            [year, month, day] if year.len() == 4 && month.len() == 2 && day.len() == 2 => (year, month, day),
+            _ => return Err(format_error()),
+        };
+        let year: i32 = year.parse().map_err(|_| format_error())?;
+        let month: u32 = month.parse().map_err(|_| format_error())?;
+        let day: u32 = day.parse().map_err(|_| format_error())?;
+        AllTime::from_ymd(year, month, day)
+    }
+
+    // every day of the given year, in order
+    #[allow(dead_code)]
+    pub fn every_day_of(year: i32) -> Vec<AllTime> {
+        let first = match chrono::NaiveDate::from_ymd_opt(year, 1, 1) {
+            Some(date) => date,
+            None => return vec![],
+        };
+        first
+            .iter_days()
+            .take_while(|date| chrono::Datelike::year(date) == year)
+            .map(AllTime::from_date)
+            .collect()
     }
 
     pub fn month(&self) -> &Month {
@@ -153,8 +645,142 @@ impl AllTime {
     }
 
     #[allow(dead_code)]
-    pub fn must_get_current_time() -> AllTime {
-        let now = chrono::Local::now();
-        return AllTime::from_datetime(now).expect("Failed to get current time");
+    pub fn year(&self) -> Option<i32> {
+        self.year
+    }
+
+    // eg. "2026-02-28"; None without a year
+    pub fn iso_date(&self) -> Option<String> {
+        Some(self.date()?.format("%Y-%m-%d").to_string())
+    }
+
+    fn date(&self) -> Option<chrono::NaiveDate> {
+        chrono::NaiveDate::from_ymd_opt(self.year?, self.month.number(), self.day_of_month)
+    }
+
+    // ISO 8601 week number (1 to 53); the first days of January can be in the last week of the year before
+    #[allow(dead_code)]
+    pub fn iso_week(&self) -> Option<u32> {
+        Some(chrono::Datelike::iso_week(&self.date()?).week())
+    }
+
+    // 1 for January 1st, up to 366 on December 31st of a leap year
+    #[allow(dead_code)]
+    pub fn day_of_year(&self) -> Option<u32> {
+        Some(chrono::Datelike::ordinal(&self.date()?))
+    }
+
+    #[allow(dead_code)]
+    pub fn hour(&self) -> Option<u32> {
+        self.time.map(|(hour, _)| hour)
+    }
+
+    #[allow(dead_code)]
+    pub fn minute(&self) -> Option<u32> {
+        self.time.map(|(_, minute)| minute)
+    }
+
+    #[allow(dead_code)]
+    pub fn season(&self) -> Season {
+        Season::from_month(&self.month)
+    }
+
+    // how a board labels the cell for one part of the point in time (see CustomBoard::labels);
+    // None when that part isn't known, eg. the year of a date made without one
+    pub fn label(&self, field: TimeField) -> Option<String> {
+        match field {
+            TimeField::Month => Some(self.month.abbreviation().to_string()),
+            TimeField::DayOfMonth => Some(self.day_of_month.to_string()),
+            TimeField::WeekDay => Some(self.week_day.abbreviation().to_string()),
+            TimeField::Year => Some(self.year?.to_string()),
+            TimeField::YearLastTwoDigits => Some(format!("'{:02}", self.year?.rem_euclid(100))),
+            TimeField::YearLastDigit => Some(format!("'{}", self.year?.rem_euclid(10))),
+            TimeField::IsoWeek => Some(format!("W{:02}", self.iso_week()?)),
+            TimeField::DayOfYear => Some(format!("D{}", self.day_of_year()?)),
+            TimeField::Hour => Some(format!("{}h", self.hour()?)),
+            TimeField::Minute => Some(format!("{:02}m", self.minute()?)),
+            TimeField::Season => Some(format!("{:?}", self.season())),
+        }
+    }
+
+    // "today" depends on where you are, so the zone has to be picked explicitly
+    pub fn current_time(zone: &Zone) -> Result<AllTime, ZoneError> {
+        let now = chrono::Utc::now();
+        let current = match zone {
+            Zone::Utc => AllTime::from_datetime(&now),
+            Zone::Local => AllTime::from_datetime(&now.with_timezone(&chrono::Local)),
+            Zone::Fixed(offset) => AllTime::from_datetime(&now.with_timezone(offset)),
+            #[cfg(feature = "tz")]
+            Zone::Named(tz) => AllTime::from_datetime(&now.with_timezone(tz)),
+        };
+        current.ok_or(ZoneError::Clock)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dates_that_are_not_in_the_calendar_are_rejected() {
+        assert_eq!(
+            AllTime::from_iso("2026-02-30").unwrap_err(),
+            DateError::NoSuchDay { year: 2026, month: 2, day: 30 }
+        );
+        // 2025 isn't a leap year, 2024 is
+        assert_eq!(
+            AllTime::from_ymd(2025, 2, 29).unwrap_err(),
+            DateError::NoSuchDay { year: 2025, month: 2, day: 29 }
+        );
+        assert_eq!(AllTime::from_iso("2024-02-29").unwrap().iso_date().as_deref(), Some("2024-02-29"));
+        assert_eq!(AllTime::from_iso("2026-3-5").unwrap_err(), DateError::Format("2026-3-5".to_string()));
+    }
+
+    #[test]
+    fn the_weekday_comes_from_the_date() {
+        let date = AllTime::from_iso("2026-03-05").unwrap();
+        assert!(matches!(date.week_day(), WeekDay::Thursday));
+        assert!(matches!(AllTime::from_ymd(2024, 2, 29).unwrap().week_day(), WeekDay::Thursday));
+        assert_eq!(date.label(TimeField::WeekDay).as_deref(), Some("Thu"));
+    }
+
+    fn offset(value: &str) -> Option<i32> {
+        match Zone::parse(value) {
+            Ok(Zone::Fixed(offset)) => Some(offset.local_minus_utc()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn zones_can_be_offsets_from_utc() {
+        assert_eq!(offset("+05:30"), Some(5 * 3600 + 30 * 60));
+        assert_eq!(offset("-08:00"), Some(-8 * 3600));
+        assert_eq!(offset("-0800"), Some(-8 * 3600));
+        assert_eq!(offset("+01"), Some(3600));
+        assert!(matches!(Zone::parse("Z"), Ok(Zone::Utc)));
+        assert!(matches!(Zone::parse("UTC"), Ok(Zone::Utc)));
+        assert!(matches!(Zone::parse("local"), Ok(Zone::Local)));
+    }
+
+    #[test]
+    fn zones_that_are_not_zones_are_rejected() {
+        for value in ["+25:00", "+05:60", "+5:30", "-08:00:00", "+ab", "somewhere"] {
+            assert_eq!(Zone::parse(value).unwrap_err(), ZoneError::Unknown(value.to_string()), "{}", value);
+        }
+    }
+
+    #[cfg(feature = "tz")]
+    #[test]
+    fn zones_can_be_named() {
+        assert!(matches!(Zone::parse("America/Halifax"), Ok(Zone::Named(chrono_tz::America::Halifax))));
+        let unknown = ZoneError::Unknown("Atlantis/Lost_City".to_string());
+        assert_eq!(Zone::parse("Atlantis/Lost_City").unwrap_err(), unknown);
+    }
+
+    #[cfg(not(feature = "tz"))]
+    #[test]
+    fn zone_names_need_the_tz_feature() {
+        let needs_tz = ZoneError::NeedsTzFeature("America/Halifax".to_string());
+        assert_eq!(Zone::parse("America/Halifax").unwrap_err(), needs_tz);
     }
 }
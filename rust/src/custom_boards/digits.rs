@@ -0,0 +1,57 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::common::{AllTime, Coordinate, CustomBoard, Target, Tile, c};
+
+// the day is spelled out with two digits ("05", "11") from a pool of twenty digit cells, where every
+// digit appears twice; most dates can be shown in several ways, and any of them will do
+#[allow(dead_code)]
+pub struct DigitsBoard {}
+impl CustomBoard for DigitsBoard {
+    fn name(&self) -> String {
+        "Digits Calendar Board".to_string()
+    }
+
+    fn tiles(&self) -> Vec<Tile> {
+        vec![
+            vec![c(0, 0), c(1, 0), c(0, 1), c(1, 1), c(0, 2)], // P
+            vec![c(0, 0), c(1, 0), c(2, 0), c(1, 1), c(1, 2)], // T
+            vec![c(0, 0), c(1, 0), c(2, 0), c(0, 1), c(0, 2)], // V
+            vec![c(0, 0), c(1, 0), c(2, 0), c(1, 1)],          // little T
+            vec![c(0, 0), c(1, 0), c(2, 0), c(3, 0)],          // line
+            vec![c(0, 0), c(1, 0), c(0, 1)],                   // corner
+            vec![c(0, 0), c(1, 0), c(2, 0)],                   // short line
+        ]
+    }
+
+    #[rustfmt::skip]
+    fn coors(&self) -> Vec<Coordinate> {
+        vec![
+            c(0,0), c(1,0), c(2,0), c(3,0), c(4,0), c(5,0), c(6,0), c(7,0),
+            c(0,1), c(1,1), c(2,1), c(3,1), c(4,1), c(5,1), c(6,1), c(7,1),
+            c(0,2), c(1,2), c(2,2), c(3,2), c(4,2), c(5,2), c(6,2), c(7,2),
+            c(0,3), c(1,3), c(2,3), c(3,3), c(4,3), c(5,3), c(6,3), c(7,3),
+        ]
+    }
+
+    #[rustfmt::skip]
+    fn labels(&self) -> HashMap<Coordinate, String> {
+        let labels = [
+            "Jan", "Feb", "Mar", "Apr", "May", "Jun", "0", "1",
+            "Jul", "Aug", "Sep", "Oct", "Nov", "Dec", "2", "3",
+            "4", "5", "6", "7", "8", "9", "0", "1",
+            "2", "3", "4", "5", "6", "7", "8", "9",
+        ];
+        self.coors().into_iter().zip(labels.iter().map(|l| l.to_string())).collect()
+    }
+
+    fn point_in_time(&self, pit: &AllTime) -> Option<HashSet<Coordinate>> {
+        self.point_in_time_alternatives(pit).into_iter().next()
+    }
+
+    fn point_in_time_alternatives(&self, pit: &AllTime) -> Vec<HashSet<Coordinate>> {
+        let day = format!("{:02}", pit.day_of_month());
+        let mut labels = vec![pit.month().abbreviation().to_string()];
+        labels.extend(day.chars().map(|digit| digit.to_string()));
+        self.target_alternatives(&Target::Labels(labels))
+    }
+}
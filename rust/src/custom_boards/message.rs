@@ -0,0 +1,39 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::common::{AllTime, Coordinate, Tile, c};
+
+const CHARACTERS: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+
+// not a calendar: every letter and digit, to spell out four character messages ("GAME", "1984", "GO4U")
+#[allow(dead_code)]
+pub struct MessageBoard {}
+impl crate::common::CustomBoard for MessageBoard {
+    fn name(&self) -> String {
+        "Message Board".to_string()
+    }
+
+    fn tiles(&self) -> Vec<Tile> {
+        vec![
+            vec![c(0, 0), c(1, 0), c(1, 1), c(2, 1), c(1, 2)], // F
+            vec![c(0, 0), c(1, 0), c(2, 0), c(3, 0), c(4, 0)], // I
+            vec![c(0, 0), c(1, 0), c(2, 0), c(3, 0), c(0, 1)], // L
+            vec![c(0, 0), c(1, 0), c(0, 1), c(1, 1), c(0, 2)], // P
+            vec![c(0, 0), c(2, 0), c(0, 1), c(1, 1), c(2, 1)], // U
+            vec![c(0, 0), c(1, 0), c(2, 0), c(3, 0), c(1, 1)], // Y
+            vec![c(0, 0), c(1, 0)],                             // domino
+        ]
+    }
+
+    fn coors(&self) -> Vec<Coordinate> {
+        (0..CHARACTERS.len() as i32).map(|i| c(i % 6, i / 6)).collect()
+    }
+
+    fn labels(&self) -> HashMap<Coordinate, String> {
+        self.coors().into_iter().zip(CHARACTERS.chars().map(|ch| ch.to_string())).collect()
+    }
+
+    // messages only: there's nothing to show a date with
+    fn point_in_time(&self, _pit: &AllTime) -> Option<HashSet<Coordinate>> {
+        None
+    }
+}
@@ -1,4 +1,29 @@
+pub mod digits;
 pub mod gmdm;
 pub mod gmdmwd;
 pub mod gt;
+pub mod hexagonal;
+pub mod message;
 pub mod nova_scotia;
+pub mod pyramid;
+pub mod soma;
+pub mod year;
+
+#[allow(dead_code)]
+pub const CUSTOM_BOARDS: [&dyn crate::common::CustomBoard; 7] = [
+    &nova_scotia::NovaScotiaCalendarBoard {},
+    &gmdm::GMDoMBoard {},
+    &gmdmwd::GMDoMWDBoard {},
+    &gt::GTBoard {},
+    &hexagonal::HexagonalCalendarBoard {},
+    &digits::DigitsBoard {},
+    &year::YearBoard {},
+];
+
+// boards that show words or numbers instead of dates (solve them with Target::Labels)
+#[allow(dead_code)]
+pub const MESSAGE_BOARDS: [&dyn crate::common::CustomBoard; 1] = [&message::MessageBoard {}];
+
+#[allow(dead_code)]
+pub const CUSTOM_POLYCUBES: [&dyn crate::polycube::PolycubePuzzle; 2] =
+    [&soma::SomaCube {}, &pyramid::TetracubePyramid {}];
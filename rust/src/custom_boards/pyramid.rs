@@ -0,0 +1,26 @@
+use crate::polycube::{Coordinate3, Cubes, Polycube, PolycubePiece, PolycubePuzzle, c3};
+
+// five tetracubes stacked into a two step pyramid (a 4x4 layer under a 2x2 one); there is only
+// one way to do it, not counting turning the whole pyramid around
+#[allow(dead_code)]
+pub struct TetracubePyramid {}
+impl PolycubePuzzle for TetracubePyramid {
+    fn name(&self) -> String {
+        "Tetracube Pyramid".to_string()
+    }
+
+    fn pieces(&self) -> Vec<PolycubePiece> {
+        let cubes: Vec<Cubes> = vec![
+            vec![c3(0, 0, 0), c3(1, 0, 0), c3(0, 1, 0), c3(1, 1, 0)], // square
+            vec![c3(0, 0, 0), c3(1, 0, 0), c3(2, 0, 0), c3(0, 1, 0)], // L
+            vec![c3(0, 0, 0), c3(1, 0, 0), c3(2, 0, 0), c3(1, 1, 0)], // T
+            vec![c3(0, 0, 0), c3(1, 0, 0), c3(1, 1, 0), c3(1, 1, 1)], // screw
+            vec![c3(0, 0, 0), c3(1, 0, 0), c3(0, 1, 0), c3(0, 0, 1)], // branch
+        ];
+        cubes.into_iter().map(PolycubePiece::new).collect()
+    }
+
+    fn cells(&self) -> Vec<Coordinate3> {
+        Polycube::pyramid(4)
+    }
+}
@@ -0,0 +1,27 @@
+use crate::polycube::{Coordinate3, Cubes, Polycube, PolycubePiece, PolycubePuzzle, c3};
+
+// Piet Hein's Soma cube: seven pieces, every irregular shape of three or four cubes, fill a 3x3x3 cube
+#[allow(dead_code)]
+pub struct SomaCube {}
+impl PolycubePuzzle for SomaCube {
+    fn name(&self) -> String {
+        "Soma Cube".to_string()
+    }
+
+    fn pieces(&self) -> Vec<PolycubePiece> {
+        let cubes: Vec<Cubes> = vec![
+            vec![c3(0, 0, 0), c3(1, 0, 0), c3(0, 1, 0)],              // V
+            vec![c3(0, 0, 0), c3(1, 0, 0), c3(2, 0, 0), c3(0, 1, 0)], // L
+            vec![c3(0, 0, 0), c3(1, 0, 0), c3(2, 0, 0), c3(1, 1, 0)], // T
+            vec![c3(0, 0, 0), c3(1, 0, 0), c3(1, 1, 0), c3(2, 1, 0)], // Z
+            vec![c3(0, 0, 0), c3(1, 0, 0), c3(1, 1, 0), c3(1, 1, 1)], // A (left-hand screw)
+            vec![c3(0, 0, 0), c3(1, 0, 0), c3(1, 1, 0), c3(0, 0, 1)], // B (right-hand screw)
+            vec![c3(0, 0, 0), c3(1, 0, 0), c3(0, 1, 0), c3(0, 0, 1)], // P (branch)
+        ];
+        cubes.into_iter().map(PolycubePiece::new).collect()
+    }
+
+    fn cells(&self) -> Vec<Coordinate3> {
+        Polycube::cuboid(3, 3, 3)
+    }
+}
@@ -0,0 +1,59 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::common::{AllTime, Coordinate, CustomBoard, Month, TimeField, Tile, c};
+
+// the (month, day-of-month) board, with a block of digits underneath for the last digit of the year
+#[allow(dead_code)]
+pub struct YearBoard {}
+impl CustomBoard for YearBoard {
+    fn name(&self) -> String {
+        "Year Calendar Board".to_string()
+    }
+
+    fn tiles(&self) -> Vec<Tile> {
+        vec![
+            vec![c(0, 0), c(1, 0), c(2, 0), c(0, 1), c(1, 1), c(2, 1)], // big rectangle
+            vec![c(0, 0), c(1, 0), c(2, 0), c(0, 1), c(2, 1)],          // horseshoe
+            vec![c(0, 0), c(1, 0), c(2, 0), c(0, 1), c(0, 2)],          // angle bracket
+            vec![c(0, 0), c(1, 0), c(2, 0), c(0, 1), c(1, 1)],          // chipped rectangle
+            vec![c(0, 0), c(1, 0), c(2, 0), c(3, 0), c(0, 1)],          // lowercase L
+            vec![c(0, 0), c(1, 0), c(2, 0), c(2, 1), c(3, 1)],          // lighting bolt
+            vec![c(0, 0), c(1, 0), c(2, 0), c(1, 1), c(1, 2)],          // uppercase T
+            vec![c(0, 0), c(1, 0), c(2, 0), c(3, 0), c(1, 1)],          // weird club
+            vec![c(0, 0), c(1, 0), c(2, 0), c(0, 1)],                   // littler L
+            vec![c(0, 0), c(1, 0), c(0, 1)],                            // corner
+            vec![c(0, 0), c(1, 0)],                                     // domino
+        ]
+    }
+
+    #[rustfmt::skip]
+    fn coors(&self) -> Vec<Coordinate> {
+        vec![
+            c(0,0), c(1,0), c(2,0), c(3,0), c(4,0), c(5,0),
+            c(0,1), c(1,1), c(2,1), c(3,1), c(4,1), c(5,1),
+            c(0,2), c(1,2), c(2,2), c(3,2), c(4,2), c(5,2), c(6,2),
+            c(0,3), c(1,3), c(2,3), c(3,3), c(4,3), c(5,3), c(6,3),
+            c(0,4), c(1,4), c(2,4), c(3,4), c(4,4), c(5,4), c(6,4),
+            c(0,5), c(1,5), c(2,5), c(3,5), c(4,5), c(5,5), c(6,5),
+            c(0,6), c(1,6), c(2,6),
+            c(0,7), c(1,7), c(2,7), c(3,7), c(4,7),
+            c(0,8), c(1,8), c(2,8), c(3,8), c(4,8),
+        ]
+    }
+
+    // months, then days 1 to 31, then the year digits ("'0" to "'9"), in the same order as coors()
+    fn labels(&self) -> HashMap<Coordinate, String> {
+        let months = (1..=12).filter_map(Month::from_u32).map(|month| month.abbreviation().to_string());
+        let days = (1..=31).map(|day: u32| day.to_string());
+        let years = (0..10).map(|digit| format!("'{}", digit));
+        self.coors().into_iter().zip(months.chain(days).chain(years)).collect()
+    }
+
+    fn time_fields(&self) -> Vec<TimeField> {
+        vec![TimeField::Month, TimeField::DayOfMonth, TimeField::YearLastDigit]
+    }
+
+    fn point_in_time(&self, pit: &AllTime) -> Option<HashSet<Coordinate>> {
+        self.point_in_time_alternatives(pit).into_iter().next()
+    }
+}
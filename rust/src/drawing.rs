@@ -0,0 +1,323 @@
+use std::collections::HashMap;
+
+use crate::common::{Coordinate, CustomBoard, PlacedPiece, Solution};
+
+// in pixels, from the top left corner of the image, y pointing down
+pub type Point = (f64, f64);
+
+// a point rounded to hundredths of a pixel, so the same corner of two neighbouring cells is recognised as such
+type Corner = (i64, i64);
+
+// the same colours the web page has always used, so pieces look the same everywhere
+pub const DEFAULT_COLORS: [&str; 12] = [
+    "#FF5733", "#33FF57", "#3357FF", "#F333FF", "#33FFF5", "#F5FF33", "#FF33A8", "#A833FF", "#33FFA8", "#FFA833",
+    "#8D33FF", "#33D1FF",
+];
+
+// how a board is drawn, the same for every image format. Colours are written the way the format takes
+// them: any SVG colour for svg (eg. "#FF5733" or "teal"), "#RRGGBB" or "#RGB" for the rest
+#[derive(Debug, Clone)]
+pub struct Style {
+    pub colors: Vec<String>,  // by piece index, wrapping around
+    pub target_color: String, // cells left uncovered to show the target
+    pub hidden: Vec<usize>,   // indices into the solution's pieces; these are left off
+    pub labels: bool,
+}
+
+impl Style {
+    pub fn new() -> Style {
+        Style {
+            colors: DEFAULT_COLORS.iter().map(|color| color.to_string()).collect(),
+            target_color: "#DDDDDD".to_string(),
+            hidden: vec![],
+            labels: true,
+        }
+    }
+
+    // an empty list keeps the defaults
+    pub fn with_colors(mut self, colors: Vec<String>) -> Style {
+        if !colors.is_empty() {
+            self.colors = colors;
+        }
+        self
+    }
+
+    pub fn with_hidden(mut self, hidden: Vec<usize>) -> Style {
+        self.hidden = hidden;
+        self
+    }
+
+    // for the formats without colour names: the ones that aren't "#RRGGBB" or "#RGB" are skipped, and
+    // the defaults are used if that leaves none
+    #[allow(dead_code)]
+    pub fn rgb_colors(&self) -> Vec<(u8, u8, u8)> {
+        let colors: Vec<(u8, u8, u8)> = self.colors.iter().filter_map(|color| hex_rgb(color)).collect();
+        if colors.is_empty() {
+            return DEFAULT_COLORS.iter().filter_map(|color| hex_rgb(color)).collect();
+        }
+        colors
+    }
+
+    #[allow(dead_code)]
+    pub fn target_rgb(&self) -> (u8, u8, u8) {
+        hex_rgb(&self.target_color).unwrap_or((0xDD, 0xDD, 0xDD))
+    }
+}
+
+// the height of a cell's label, as a fraction of the cell size: smaller for longer labels, so they fit
+pub fn label_size(label: &str) -> f64 {
+    (0.32_f64).min(0.9 / label.chars().count().max(1) as f64)
+}
+
+// a board laid out for drawing, the same for every image format: where each cell goes, which ones are
+// shaded, and where each piece and its outline are
+pub struct Drawing {
+    pub name: String,
+    pub width: f64,
+    pub height: f64,
+    pub cells: Vec<DrawnCell>,
+    pub pieces: Vec<DrawnPiece>,
+    pub border: Vec<(Point, Point)>, // around the whole board
+}
+
+pub struct DrawnCell {
+    pub polygon: Vec<Point>,
+    pub label: Option<String>,
+    pub target: bool, // left uncovered to show the target
+}
+
+pub struct DrawnPiece {
+    pub piece: usize, // index into CustomBoard::pieces(), eg. to pick a colour
+    pub copy: usize,
+    pub cells: Vec<Vec<Point>>,
+    pub outline: Vec<(Point, Point)>, // the edges the piece doesn't share with itself
+}
+
+impl Drawing {
+    // cell_size is the distance between neighbouring cells, in pixels; margin is left on every side
+    pub fn new(
+        board: &dyn CustomBoard,
+        target: &[Coordinate],
+        pieces: &[&PlacedPiece],
+        cell_size: f64,
+        margin: f64,
+    ) -> Drawing {
+        let lattice = board.lattice();
+        let coors = board.coors();
+        let scaled = |coor: &Coordinate| -> Vec<Point> {
+            lattice
+                .cell_polygon(coor)
+                .into_iter()
+                .map(|(x, y)| (x * cell_size, y * cell_size))
+                .collect()
+        };
+
+        let polygons: Vec<Vec<Point>> = coors.iter().map(scaled).collect();
+        let points = || polygons.iter().flatten();
+        let min_x = points().map(|(x, _)| *x).fold(f64::INFINITY, f64::min);
+        let min_y = points().map(|(_, y)| *y).fold(f64::INFINITY, f64::min);
+        let max_x = points().map(|(x, _)| *x).fold(f64::NEG_INFINITY, f64::max);
+        let max_y = points().map(|(_, y)| *y).fold(f64::NEG_INFINITY, f64::max);
+        let shift = |polygon: Vec<Point>| -> Vec<Point> {
+            polygon
+                .into_iter()
+                .map(|(x, y)| (x - min_x + margin, y - min_y + margin))
+                .collect()
+        };
+
+        let labels = board.labels();
+        let cells: Vec<DrawnCell> = coors
+            .iter()
+            .zip(polygons.iter())
+            .map(|(coor, polygon)| DrawnCell {
+                polygon: shift(polygon.clone()),
+                label: labels.get(coor).cloned(),
+                target: target.contains(coor),
+            })
+            .collect();
+        let border = outline(&cells.iter().map(|cell| cell.polygon.clone()).collect::<Vec<_>>());
+
+        let pieces: Vec<DrawnPiece> = pieces
+            .iter()
+            .map(|placed| {
+                let cells: Vec<Vec<Point>> = placed.tile.iter().map(|coor| shift(scaled(coor))).collect();
+                DrawnPiece {
+                    piece: placed.piece,
+                    copy: placed.copy,
+                    outline: outline(&cells),
+                    cells,
+                }
+            })
+            .collect();
+
+        Drawing {
+            name: board.name(),
+            width: max_x - min_x + 2.0 * margin,
+            height: max_y - min_y + 2.0 * margin,
+            cells,
+            pieces,
+            border,
+        }
+    }
+
+    // the solution with the pieces at the hidden indices left off; its target (and any gaps) are shaded
+    pub fn solution(
+        board: &dyn CustomBoard,
+        solution: &Solution,
+        hidden: &[usize],
+        cell_size: f64,
+        margin: f64,
+    ) -> Drawing {
+        let mut target = solution.target.clone();
+        target.extend(solution.gaps.iter().copied());
+        let shown: Vec<&PlacedPiece> = solution
+            .pieces
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !hidden.contains(i))
+            .map(|(_, placed)| placed)
+            .collect();
+        Drawing::new(board, &target, &shown, cell_size, margin)
+    }
+}
+
+// "#RRGGBB" or "#RGB", for the formats that have no colour names of their own
+#[allow(dead_code)]
+pub fn hex_rgb(value: &str) -> Option<(u8, u8, u8)> {
+    let hex = value.trim().strip_prefix('#')?;
+    let digits: Vec<u8> = hex
+        .chars()
+        .map(|ch| ch.to_digit(16).map(|digit| digit as u8))
+        .collect::<Option<Vec<u8>>>()?;
+    match digits.as_slice() {
+        [r, g, b] => Some((r * 17, g * 17, b * 17)),
+        [r1, r2, g1, g2, b1, b2] => Some((r1 * 16 + r2, g1 * 16 + g2, b1 * 16 + b2)),
+        _ => None,
+    }
+}
+
+// the middle of a cell, for its label
+pub fn center(polygon: &[Point]) -> Point {
+    let count = polygon.len().max(1) as f64;
+    (
+        polygon.iter().map(|(x, _)| x).sum::<f64>() / count,
+        polygon.iter().map(|(_, y)| y).sum::<f64>() / count,
+    )
+}
+
+// the edges that only one of the polygons has; shared edges are inside the shape. Each edge keeps the
+// direction its polygon gave it, so (as every lattice lists its corners the same way round) the
+// inside of the shape is always on the same side
+pub fn outline(polygons: &[Vec<Point>]) -> Vec<(Point, Point)> {
+    let mut edges: HashMap<(Corner, Corner), usize> = HashMap::new();
+    let mut ordered: Vec<(Point, Point)> = Vec::new();
+    for polygon in polygons {
+        for i in 0..polygon.len() {
+            let (from, to) = (polygon[i], polygon[(i + 1) % polygon.len()]);
+            let count = edges.entry(edge_key(from, to)).or_insert(0);
+            *count += 1;
+            if *count == 1 {
+                ordered.push((from, to));
+            }
+        }
+    }
+
+    ordered
+        .into_iter()
+        .filter(|(from, to)| edges[&edge_key(*from, *to)] == 1)
+        .collect()
+}
+
+// outline edges joined up into closed loops, without the corners where the loop goes straight on (eg.
+// between two cells of a straight row)
+#[allow(dead_code)]
+pub fn loops(edges: &[(Point, Point)]) -> Vec<Vec<Point>> {
+    chains(edges).into_iter().map(straightened).collect()
+}
+
+// outline edges joined up into closed loops, keeping every corner, so the loops still meet whatever
+// else was built from the same edges
+#[allow(dead_code)]
+pub fn chains(edges: &[(Point, Point)]) -> Vec<Vec<Point>> {
+    let corner = |(x, y): Point| ((x * 100.0).round() as i64, (y * 100.0).round() as i64);
+    let mut starting: HashMap<Corner, Vec<usize>> = HashMap::new();
+    for (i, (from, _)) in edges.iter().enumerate() {
+        starting.entry(corner(*from)).or_default().push(i);
+    }
+
+    let mut used = vec![false; edges.len()];
+    let mut chains: Vec<Vec<Point>> = Vec::new();
+    for first in 0..edges.len() {
+        if used[first] {
+            continue;
+        }
+        let mut points: Vec<Point> = Vec::new();
+        let mut current = first;
+        loop {
+            used[current] = true;
+            let (from, to) = edges[current];
+            points.push(from);
+            let next = starting
+                .get(&corner(to))
+                .and_then(|candidates| candidates.iter().find(|i| !used[**i]));
+            match next {
+                Some(next) => current = *next,
+                None => break,
+            }
+        }
+        chains.push(points);
+    }
+    chains
+}
+
+// without the corners that lie on a straight line between their neighbours
+fn straightened(points: Vec<Point>) -> Vec<Point> {
+    let count = points.len();
+    (0..count)
+        .filter(|i| {
+            let (before, at, after) = (points[(i + count - 1) % count], points[*i], points[(i + 1) % count]);
+            let cross = (at.0 - before.0) * (after.1 - at.1) - (at.1 - before.1) * (after.0 - at.0);
+            cross.abs() > 1e-9
+        })
+        .map(|i| points[i])
+        .collect()
+}
+
+// twice the area, positive when the corners go clockwise on screen (as every lattice's cells do)
+#[allow(dead_code)]
+pub fn signed_area(polygon: &[Point]) -> f64 {
+    (0..polygon.len())
+        .map(|i| {
+            let ((x1, y1), (x2, y2)) = (polygon[i], polygon[(i + 1) % polygon.len()]);
+            x1 * y2 - x2 * y1
+        })
+        .sum()
+}
+
+// every edge of a loop from loops() moved out by the distance (or in, when it's negative), with the
+// corners where the moved edges meet; winding is the signed_area() of one of the cells the loop came from
+#[allow(dead_code)]
+pub fn offset(points: &[Point], distance: f64, winding: f64) -> Vec<Point> {
+    let count = points.len();
+    let side = if winding < 0.0 { -1.0 } else { 1.0 };
+    let normal = |from: Point, to: Point| -> Point {
+        let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+        let length = (dx * dx + dy * dy).sqrt().max(1e-12);
+        (side * dy / length, side * -dx / length)
+    };
+    (0..count)
+        .map(|i| {
+            let (before, at, after) = (points[(i + count - 1) % count], points[i], points[(i + 1) % count]);
+            let (n1, n2) = (normal(before, at), normal(at, after));
+            let scale = distance / (1.0 + n1.0 * n2.0 + n1.1 * n2.1).max(1e-6);
+            (at.0 + (n1.0 + n2.0) * scale, at.1 + (n1.1 + n2.1) * scale)
+        })
+        .collect()
+}
+
+// the same either way round
+fn edge_key(from: Point, to: Point) -> (Corner, Corner) {
+    let corner = |(x, y): Point| ((x * 100.0).round() as i64, (y * 100.0).round() as i64);
+    let (a, b) = (corner(from), corner(to));
+    if a < b { (a, b) } else { (b, a) }
+}
@@ -0,0 +1,183 @@
+// Knuth's Algorithm X: every column has to be covered a set number of times by the chosen rows,
+// and each step branches on the column with the fewest rows left that could cover it
+//
+// for packing, the rows are placements and the columns are the open cells (covered once) and the
+// pieces (covered once per copy). A column is only branched on once it needs exactly one more row,
+// so choosing identical copies of a piece in a different order never shows up as a new solution
+pub struct ExactCover {
+    rows: Vec<Vec<usize>>,        // the columns each row covers
+    column_rows: Vec<Vec<usize>>, // the rows that cover each column
+    needed: Vec<usize>,           // how many more times each column has to be covered
+    available: Vec<usize>,        // how many rows that could still be chosen cover each column
+    blocked: Vec<usize>,          // how many full columns each row touches; only rows at 0 can be chosen
+}
+
+enum Step {
+    Done,          // every column is covered
+    Stuck,         // some column can no longer be covered
+    Branch(usize), // try each row that covers this column
+}
+
+impl ExactCover {
+    pub fn new(needed: Vec<usize>, rows: Vec<Vec<usize>>) -> ExactCover {
+        let mut column_rows: Vec<Vec<usize>> = vec![vec![]; needed.len()];
+        for (row, columns) in rows.iter().enumerate() {
+            for column in columns.iter() {
+                column_rows[*column].push(row);
+            }
+        }
+
+        ExactCover {
+            available: column_rows.iter().map(|rows| rows.len()).collect(),
+            blocked: vec![0; rows.len()],
+            rows,
+            column_rows,
+            needed,
+        }
+    }
+
+    // the rows of the first solution found
+    pub fn solve(&mut self) -> Option<Vec<usize>> {
+        let mut chosen: Vec<usize> = Vec::new();
+        if self.search(&mut chosen) {
+            Some(chosen)
+        } else {
+            None
+        }
+    }
+
+    pub fn count(&mut self) -> usize {
+        let column = match self.next_step() {
+            Step::Done => return 1,
+            Step::Stuck => return 0,
+            Step::Branch(column) => column,
+        };
+
+        let mut found = 0;
+        for row in self.candidates(column) {
+            self.select(row);
+            found += self.count();
+            self.deselect(row);
+        }
+        found
+    }
+
+    // hands the rows of each solution to visit, until it returns false
+    #[allow(dead_code)]
+    pub fn each(&mut self, visit: &mut dyn FnMut(&[usize]) -> bool) {
+        let mut chosen: Vec<usize> = Vec::new();
+        self.visit_all(&mut chosen, visit);
+    }
+
+    // false once visit has asked to stop
+    fn visit_all(&mut self, chosen: &mut Vec<usize>, visit: &mut dyn FnMut(&[usize]) -> bool) -> bool {
+        let column = match self.next_step() {
+            Step::Done => return visit(chosen),
+            Step::Stuck => return true,
+            Step::Branch(column) => column,
+        };
+
+        for row in self.candidates(column) {
+            self.select(row);
+            chosen.push(row);
+            let keep_going = self.visit_all(chosen, visit);
+            chosen.pop();
+            self.deselect(row);
+            if !keep_going {
+                return false;
+            }
+        }
+        true
+    }
+
+    fn search(&mut self, chosen: &mut Vec<usize>) -> bool {
+        let column = match self.next_step() {
+            Step::Done => return true,
+            Step::Stuck => return false,
+            Step::Branch(column) => column,
+        };
+
+        for row in self.candidates(column) {
+            self.select(row);
+            chosen.push(row);
+            if self.search(chosen) {
+                return true;
+            }
+            chosen.pop();
+            self.deselect(row);
+        }
+        false
+    }
+
+    fn next_step(&self) -> Step {
+        let mut best: Option<usize> = None;
+        let mut any_needed = false;
+        for column in 0..self.needed.len() {
+            if self.needed[column] == 0 {
+                continue;
+            }
+            any_needed = true;
+            if self.available[column] < self.needed[column] {
+                return Step::Stuck;
+            }
+            if self.needed[column] == 1 && best.is_none_or(|b| self.available[column] < self.available[b]) {
+                best = Some(column);
+            }
+        }
+
+        match (any_needed, best) {
+            (false, _) => Step::Done,
+            (true, Some(column)) => Step::Branch(column),
+            (true, None) => Step::Stuck, // only pieces with several copies left, and no cells to put them on
+        }
+    }
+
+    fn candidates(&self, column: usize) -> Vec<usize> {
+        self.column_rows[column].iter().copied().filter(|row| self.blocked[*row] == 0).collect()
+    }
+
+    fn select(&mut self, row: usize) {
+        for i in 0..self.rows[row].len() {
+            let column = self.rows[row][i];
+            self.needed[column] -= 1;
+            if self.needed[column] == 0 {
+                for j in 0..self.column_rows[column].len() {
+                    let other = self.column_rows[column][j];
+                    self.block(other);
+                }
+            }
+        }
+    }
+
+    // exactly undoes select, in reverse order
+    fn deselect(&mut self, row: usize) {
+        for i in (0..self.rows[row].len()).rev() {
+            let column = self.rows[row][i];
+            if self.needed[column] == 0 {
+                for j in (0..self.column_rows[column].len()).rev() {
+                    let other = self.column_rows[column][j];
+                    self.unblock(other);
+                }
+            }
+            self.needed[column] += 1;
+        }
+    }
+
+    fn block(&mut self, row: usize) {
+        self.blocked[row] += 1;
+        if self.blocked[row] == 1 {
+            for column in self.rows[row].iter() {
+                self.available[*column] -= 1;
+            }
+        }
+    }
+
+    fn unblock(&mut self, row: usize) {
+        self.blocked[row] -= 1;
+        if self.blocked[row] == 0 {
+            for column in self.rows[row].iter() {
+                self.available[*column] += 1;
+            }
+        }
+    }
+}
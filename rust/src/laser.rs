@@ -0,0 +1,250 @@
+use std::fmt::Write;
+
+use crate::common::{Coordinate, CustomBoard};
+use crate::drawing::{Point, center, label_size, loops, offset, outline, signed_area};
+use crate::svg::escape;
+
+// everything needed to cut a board out of sheet material, laid out on one sheet in millimetres: a frame
+// with the board's shape cut out of it, a base plate (the same size) with the labels engraved on it to
+// glue under the frame, and every piece of the kit. Cut lines are moved out by half the kerf around the
+// plates and pieces, and in around the hole, so everything comes out the size it was drawn
+pub struct LaserCut {
+    cell_size: f64, // in millimetres, between neighbouring cells
+    kerf: f64,      // the width of material the laser burns away, in millimetres
+}
+
+// the parts of a board, ready to cut
+pub struct CutSheet {
+    pub name: String,
+    pub width: f64,
+    pub height: f64,
+    pub cuts: Vec<Vec<Point>>, // closed paths
+    pub engravings: Vec<Engraving>,
+}
+
+pub struct Engraving {
+    pub text: String,
+    pub at: Point, // the middle of the text
+    pub size: f64, // the height of the text
+}
+
+impl LaserCut {
+    pub fn new() -> LaserCut {
+        LaserCut {
+            cell_size: 20.0,
+            kerf: 0.15,
+        }
+    }
+
+    pub fn with_cell_size(mut self, cell_size: f64) -> LaserCut {
+        self.cell_size = cell_size;
+        self
+    }
+
+    pub fn with_kerf(mut self, kerf: f64) -> LaserCut {
+        self.kerf = kerf;
+        self
+    }
+
+    pub fn cell_size(&self) -> f64 {
+        self.cell_size
+    }
+
+    pub fn kerf(&self) -> f64 {
+        self.kerf
+    }
+
+    // the frame and base plate side by side, and the pieces in rows underneath
+    pub fn sheet(&self, board: &dyn CustomBoard) -> CutSheet {
+        let lattice = board.lattice();
+        let scaled = |coor: &Coordinate| -> Vec<Point> {
+            lattice
+                .cell_polygon(coor)
+                .into_iter()
+                .map(|(x, y)| (x * self.cell_size, y * self.cell_size))
+                .collect()
+        };
+        let coors = board.coors();
+        let cells: Vec<Vec<Point>> = coors.iter().map(scaled).collect();
+        let winding = cells.first().map(|cell| signed_area(cell)).unwrap_or(1.0);
+        let spacing = self.cell_size / 4.0;
+        let mut cuts: Vec<Vec<Point>> = Vec::new();
+        let mut engravings: Vec<Engraving> = Vec::new();
+
+        // the frame, with a border half a cell wide around the board
+        let border = self.cell_size / 2.0;
+        let (min_x, min_y, max_x, max_y) = bounds(&cells);
+        let (plate_width, plate_height) = (max_x - min_x + 2.0 * border, max_y - min_y + 2.0 * border);
+        let (shift_x, shift_y) = (border - min_x, border - min_y);
+        let plate = |left: f64| -> Vec<Point> {
+            let corners = [
+                (left, 0.0),
+                (left + plate_width, 0.0),
+                (left + plate_width, plate_height),
+                (left, plate_height),
+            ];
+            offset(&corners, self.kerf / 2.0, signed_area(&corners))
+        };
+        cuts.push(plate(0.0));
+        for hole in loops(&outline(&cells)) {
+            cuts.push(translated(&offset(&hole, -self.kerf / 2.0, winding), shift_x, shift_y));
+        }
+
+        // the base plate, to the right of the frame
+        let base_left = plate_width + spacing;
+        cuts.push(plate(base_left));
+        let labels = board.labels();
+        for (coor, cell) in coors.iter().zip(cells.iter()) {
+            if let Some(label) = labels.get(coor) {
+                let (x, y) = center(cell);
+                engravings.push(Engraving {
+                    text: label.clone(),
+                    at: (x + shift_x + base_left, y + shift_y),
+                    size: self.cell_size * label_size(label),
+                });
+            }
+        }
+
+        // the pieces, in rows as wide as both plates
+        let sheet_width = 2.0 * plate_width + spacing;
+        let (mut x, mut y, mut row_height) = (0.0, plate_height + spacing, 0.0_f64);
+        for piece in board.pieces().iter() {
+            for _ in 0..piece.count {
+                let cells: Vec<Vec<Point>> = piece.tile.iter().map(scaled).collect();
+                let (min_x, min_y, max_x, max_y) = bounds(&cells);
+                let (width, height) = (max_x - min_x, max_y - min_y);
+                if x > 0.0 && x + width > sheet_width {
+                    (x, y, row_height) = (0.0, y + row_height + spacing, 0.0);
+                }
+                for edge in loops(&outline(&cells)) {
+                    cuts.push(translated(&offset(&edge, self.kerf / 2.0, winding), x - min_x, y - min_y));
+                }
+                x += width + spacing;
+                row_height = row_height.max(height);
+            }
+        }
+
+        // a little room around everything, for the kerf
+        let (width, height) = (sheet_width.max(x - spacing), y + row_height);
+        let margin = self.kerf;
+        CutSheet {
+            name: board.name(),
+            width: width + 2.0 * margin,
+            height: height + 2.0 * margin,
+            cuts: cuts.iter().map(|path| translated(path, margin, margin)).collect(),
+            engravings: engravings
+                .into_iter()
+                .map(|engraving| Engraving {
+                    at: (engraving.at.0 + margin, engraving.at.1 + margin),
+                    ..engraving
+                })
+                .collect(),
+        }
+    }
+
+    // sized in millimetres, with the cuts (red hairlines) and engravings (blue) on layers of their own,
+    // as most laser software expects
+    pub fn svg(&self, board: &dyn CustomBoard) -> String {
+        let sheet = self.sheet(board);
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:inkscape="http://www.inkscape.org/namespaces/inkscape" viewBox="0 0 {:.3} {:.3}" width="{:.3}mm" height="{:.3}mm">"#,
+            sheet.width, sheet.height, sheet.width, sheet.height
+        );
+        let _ = writeln!(svg, "<title>{}</title>", escape(&sheet.name));
+        let _ = writeln!(
+            svg,
+            r##"<g id="cut" inkscape:groupmode="layer" inkscape:label="cut" fill="none" stroke="#FF0000" stroke-width="0.05">"##
+        );
+        for path in sheet.cuts.iter() {
+            let points: Vec<String> = path.iter().map(|(x, y)| format!("{:.3} {:.3}", x, y)).collect();
+            let _ = writeln!(svg, r#"<path d="M{}Z"/>"#, points.join(" L"));
+        }
+        let _ = writeln!(svg, "</g>");
+        let _ = writeln!(
+            svg,
+            r##"<g id="engrave" inkscape:groupmode="layer" inkscape:label="engrave" fill="#0000FF" font-family="sans-serif" text-anchor="middle" dominant-baseline="central">"##
+        );
+        for engraving in sheet.engravings.iter() {
+            let _ = writeln!(
+                svg,
+                r#"<text x="{:.3}" y="{:.3}" font-size="{:.2}">{}</text>"#,
+                engraving.at.0,
+                engraving.at.1,
+                engraving.size,
+                escape(&engraving.text)
+            );
+        }
+        let _ = writeln!(svg, "</g>");
+        let _ = writeln!(svg, "</svg>");
+        svg
+    }
+
+    // AutoCAD R12 DXF (y pointing up, as DXF has it): closed polylines on the CUT layer, and text on the
+    // ENGRAVE layer. R12 has no setting for units, so the numbers are millimetres as they stand
+    pub fn dxf(&self, board: &dyn CustomBoard) -> String {
+        let sheet = self.sheet(board);
+        let flip = |y: f64| sheet.height - y;
+        let mut dxf = String::new();
+        let mut group = |code: u32, value: &str| {
+            let _ = writeln!(dxf, "{}\n{}", code, value);
+        };
+
+        group(0, "SECTION");
+        group(2, "HEADER");
+        group(9, "$ACADVER");
+        group(1, "AC1009");
+        group(0, "ENDSEC");
+
+        group(0, "SECTION");
+        group(2, "ENTITIES");
+        for path in sheet.cuts.iter() {
+            group(0, "POLYLINE");
+            group(8, "CUT");
+            group(66, "1");
+            group(70, "1"); // closed
+            for (x, y) in path.iter() {
+                group(0, "VERTEX");
+                group(8, "CUT");
+                group(10, &format!("{:.4}", x));
+                group(20, &format!("{:.4}", flip(*y)));
+                group(30, "0.0");
+            }
+            group(0, "SEQEND");
+            group(8, "CUT");
+        }
+        for engraving in sheet.engravings.iter() {
+            let (x, y) = (format!("{:.4}", engraving.at.0), format!("{:.4}", flip(engraving.at.1)));
+            group(0, "TEXT");
+            group(8, "ENGRAVE");
+            group(10, &x);
+            group(20, &y);
+            group(30, "0.0");
+            group(40, &format!("{:.4}", engraving.size));
+            group(1, &engraving.text);
+            group(72, "1"); // centred
+            group(73, "2"); // on the middle of the text
+            group(11, &x);
+            group(21, &y);
+            group(31, "0.0");
+        }
+        group(0, "ENDSEC");
+        group(0, "EOF");
+        dxf
+    }
+}
+
+fn bounds(polygons: &[Vec<Point>]) -> (f64, f64, f64, f64) {
+    let points = || polygons.iter().flatten();
+    (
+        points().map(|(x, _)| *x).fold(f64::INFINITY, f64::min),
+        points().map(|(_, y)| *y).fold(f64::INFINITY, f64::min),
+        points().map(|(x, _)| *x).fold(f64::NEG_INFINITY, f64::max),
+        points().map(|(_, y)| *y).fold(f64::NEG_INFINITY, f64::max),
+    )
+}
+
+fn translated(path: &[Point], dx: f64, dy: f64) -> Vec<Point> {
+    path.iter().map(|(x, y)| (x + dx, y + dy)).collect()
+}
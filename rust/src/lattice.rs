@@ -0,0 +1,274 @@
+use crate::common::{Coordinate, Tile};
+use crate::tile_helper::TileHelper;
+
+// height of a row of triangles with unit sides
+const TRIANGLE_HEIGHT: f64 = 0.866_025_403_784_438_6;
+
+// the grid the cells of a board (and its pieces) sit on
+//
+// Square: the usual (x, y) grid
+// Hexagonal: "pointy top" hexagons in axial coordinates; each row (y) is shifted half a cell right
+//   of the one above it, so (x, y) touches (x + 1, y - 1) and (x - 1, y + 1) as well as the 4 square neighbours
+// Triangular: rows (y) of alternating triangles; (x, y) points up when x + y is even, and down otherwise
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lattice {
+    Square,
+    Hexagonal,
+    Triangular,
+}
+
+impl Lattice {
+    // how many times rotate() can be applied before a tile is back where it started
+    pub fn rotation_count(&self) -> u32 {
+        match self {
+            Lattice::Square => 4,
+            Lattice::Hexagonal | Lattice::Triangular => 6,
+        }
+    }
+
+    // rotate by the smallest step the lattice allows (90 degrees for squares, 60 for hexagons and triangles),
+    // around tile[0] (or one of its corners, for triangles)
+    pub fn rotate(&self, tile: &mut Tile) {
+        match self {
+            Lattice::Square => TileHelper::rotate(tile),
+            Lattice::Hexagonal => {
+                let origin = tile[0];
+                for coor in tile.iter_mut().skip(1) {
+                    let relative_x = coor.x - origin.x;
+                    let relative_y = coor.y - origin.y;
+                    coor.x = origin.x - relative_y;
+                    coor.y = origin.y + relative_x + relative_y;
+                }
+            }
+            Lattice::Triangular => {
+                let (origin_x, origin_y) = Self::triangle_corner(&tile[0]);
+                let (sin, cos) = std::f64::consts::FRAC_PI_3.sin_cos();
+                for coor in tile.iter_mut() {
+                    let (x, y) = Self::triangle_center(coor);
+                    let (relative_x, relative_y) = (x - origin_x, y - origin_y);
+                    *coor = Self::triangle_at(
+                        origin_x + relative_x * cos - relative_y * sin,
+                        origin_y + relative_x * sin + relative_y * cos,
+                    );
+                }
+            }
+        }
+    }
+
+    // flip horizontally around tile[0] (or one of its corners, for triangles)
+    pub fn flip(&self, tile: &mut Tile) {
+        match self {
+            Lattice::Square => TileHelper::flip(tile),
+            Lattice::Hexagonal => {
+                let origin = tile[0];
+                for coor in tile.iter_mut().skip(1) {
+                    let relative_x = coor.x - origin.x;
+                    let relative_y = coor.y - origin.y;
+                    coor.x = origin.x - relative_x - relative_y;
+                }
+            }
+            Lattice::Triangular => {
+                let (origin_x, _) = Self::triangle_corner(&tile[0]);
+                for coor in tile.iter_mut() {
+                    let (x, y) = Self::triangle_center(coor);
+                    *coor = Self::triangle_at(2.0 * origin_x - x, y);
+                }
+            }
+        }
+    }
+
+    // every cell sharing an edge with the coordinate
+    pub fn neighbors(&self, coor: &Coordinate) -> Vec<Coordinate> {
+        let (x, y) = (coor.x, coor.y);
+        let mut out = vec![
+            Coordinate { x: x + 1, y },
+            Coordinate { x: x - 1, y },
+        ];
+        match self {
+            Lattice::Square => {
+                out.push(Coordinate { x, y: y + 1 });
+                out.push(Coordinate { x, y: y - 1 });
+            }
+            Lattice::Hexagonal => {
+                out.push(Coordinate { x, y: y + 1 });
+                out.push(Coordinate { x, y: y - 1 });
+                out.push(Coordinate { x: x + 1, y: y - 1 });
+                out.push(Coordinate { x: x - 1, y: y + 1 });
+            }
+            Lattice::Triangular => {
+                if Self::points_up(coor) {
+                    out.push(Coordinate { x, y: y + 1 });
+                } else {
+                    out.push(Coordinate { x, y: y - 1 });
+                }
+            }
+        }
+        out
+    }
+
+    // can a tile be moved by the offset without changing shape?
+    // (moving a triangle an odd number of cells turns it upside down)
+    #[inline]
+    pub fn is_valid_offset(&self, offset: &Coordinate) -> bool {
+        match self {
+            Lattice::Square | Lattice::Hexagonal => true,
+            Lattice::Triangular => (offset.x + offset.y).rem_euclid(2) == 0,
+        }
+    }
+
+    // sorted coordinates, moved (as little as possible) so the smallest x and y are both 0 or close to it
+    pub fn normalized(&self, tile: &Tile) -> Tile {
+        let mut out = TileHelper::normalized(tile);
+        if *self == Lattice::Triangular {
+            let min_x = tile.iter().map(|coor| coor.x).min().unwrap_or(0);
+            let min_y = tile.iter().map(|coor| coor.y).min().unwrap_or(0);
+            if !self.is_valid_offset(&Coordinate { x: min_x, y: min_y }) {
+                TileHelper::translate(&mut out, &Coordinate { x: 1, y: 0 });
+            }
+        }
+        out
+    }
+
+    // do both tiles cover the same cells, once moved to the same position?
+    pub fn same_shape(&self, a: &Tile, b: &Tile) -> bool {
+        self.normalized(a) == self.normalized(b)
+    }
+
+    // corners of the cell, for drawing; neighbouring cells are one unit apart
+    #[allow(dead_code)]
+    pub fn cell_polygon(&self, coor: &Coordinate) -> Vec<(f64, f64)> {
+        let (x, y) = (coor.x as f64, coor.y as f64);
+        match self {
+            Lattice::Square => vec![(x, y), (x + 1.0, y), (x + 1.0, y + 1.0), (x, y + 1.0)],
+            Lattice::Hexagonal => {
+                let radius = 1.0 / 3.0_f64.sqrt();
+                let (center_x, center_y) = (x + y / 2.0 + 0.5, y * 1.5 * radius + radius);
+                (0..6)
+                    .map(|i| {
+                        let angle = std::f64::consts::FRAC_PI_3 * i as f64 - std::f64::consts::FRAC_PI_2;
+                        (center_x + radius * angle.cos(), center_y + radius * angle.sin())
+                    })
+                    .collect()
+            }
+            Lattice::Triangular => {
+                let (left, top, bottom) = (x / 2.0, y * TRIANGLE_HEIGHT, (y + 1.0) * TRIANGLE_HEIGHT);
+                if Self::points_up(coor) {
+                    vec![(left + 0.5, top), (left + 1.0, bottom), (left, bottom)]
+                } else {
+                    vec![(left, top), (left + 1.0, top), (left + 0.5, bottom)]
+                }
+            }
+        }
+    }
+
+    // the radius of the biggest circle that fits in a cell, for cells cell_polygon() sizes
+    #[allow(dead_code)]
+    pub fn inscribed_radius(&self) -> f64 {
+        match self {
+            Lattice::Square | Lattice::Hexagonal => 0.5,
+            Lattice::Triangular => TRIANGLE_HEIGHT / 3.0,
+        }
+    }
+
+    // where to print the cell in a plain text drawing, as (column, row); squares take two columns
+    pub fn text_position(&self, coor: &Coordinate) -> (i32, i32) {
+        match self {
+            Lattice::Square => (coor.x * 2, coor.y),
+            Lattice::Hexagonal => (coor.x * 2 + coor.y, coor.y),
+            Lattice::Triangular => (coor.x, coor.y),
+        }
+    }
+
+    #[inline]
+    fn points_up(coor: &Coordinate) -> bool {
+        (coor.x + coor.y).rem_euclid(2) == 0
+    }
+
+    fn triangle_center(coor: &Coordinate) -> (f64, f64) {
+        let row_offset = if Self::points_up(coor) { 2.0 } else { 1.0 } * TRIANGLE_HEIGHT / 3.0;
+        (coor.x as f64 / 2.0 + 0.5, coor.y as f64 * TRIANGLE_HEIGHT + row_offset)
+    }
+
+    // the triangle containing the given point (only ever called with points near a triangle's center)
+    fn triangle_at(x: f64, y: f64) -> Coordinate {
+        Coordinate {
+            x: ((x - 0.5) * 2.0).round() as i32,
+            y: (y / TRIANGLE_HEIGHT).floor() as i32,
+        }
+    }
+
+    // the top corner of an upwards triangle, or the bottom corner of a downwards one
+    fn triangle_corner(coor: &Coordinate) -> (f64, f64) {
+        let row = if Self::points_up(coor) { coor.y } else { coor.y + 1 };
+        (coor.x as f64 / 2.0 + 0.5, row as f64 * TRIANGLE_HEIGHT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::c;
+
+    const LATTICES: [Lattice; 3] = [Lattice::Square, Lattice::Hexagonal, Lattice::Triangular];
+
+    // a shape with no symmetry on each lattice, so every rotation and flip of it looks different;
+    // for triangles, one starting on an upwards cell and one on a downwards cell
+    fn lopsided(lattice: Lattice) -> Vec<Tile> {
+        match lattice {
+            Lattice::Square => vec![vec![c(0, 0), c(0, 1), c(0, 2), c(1, 2), c(2, 1)]],
+            Lattice::Hexagonal => vec![vec![c(0, 0), c(1, 0), c(2, 0), c(2, 1), c(0, 3)]],
+            Lattice::Triangular => vec![
+                vec![c(0, 0), c(1, 0), c(2, 0), c(3, 0), c(3, 1)],
+                vec![c(1, 0), c(2, 0), c(3, 0), c(4, 0), c(4, 1)],
+            ],
+        }
+    }
+
+    #[test]
+    fn rotating_all_the_way_round_is_the_identity() {
+        for lattice in LATTICES {
+            for tile in lopsided(lattice) {
+                let mut turned = tile.clone();
+                for step in 1..=lattice.rotation_count() {
+                    lattice.rotate(&mut turned);
+                    let back = step == lattice.rotation_count();
+                    assert_eq!(lattice.same_shape(&turned, &tile), back, "{:?} {:?} after {}", lattice, tile, step);
+                }
+                assert_eq!(turned, tile, "{:?}", lattice);
+            }
+        }
+    }
+
+    #[test]
+    fn flipping_twice_is_the_identity() {
+        for lattice in LATTICES {
+            for tile in lopsided(lattice) {
+                let mut flipped = tile.clone();
+                lattice.flip(&mut flipped);
+                assert!(!lattice.same_shape(&flipped, &tile), "{:?} {:?}", lattice, tile);
+                lattice.flip(&mut flipped);
+                assert_eq!(flipped, tile, "{:?}", lattice);
+            }
+        }
+    }
+
+    // a cell is always one of its neighbours' neighbours, so pieces connect the same either way round
+    #[test]
+    fn neighbours_go_both_ways() {
+        for lattice in LATTICES {
+            for coor in [c(0, 0), c(1, 0), c(3, 4), c(4, 4)] {
+                let neighbors = lattice.neighbors(&coor);
+                let expected = match lattice {
+                    Lattice::Square => 4,
+                    Lattice::Hexagonal => 6,
+                    Lattice::Triangular => 3,
+                };
+                assert_eq!(neighbors.len(), expected, "{:?}", lattice);
+                for neighbor in neighbors {
+                    assert!(lattice.neighbors(&neighbor).contains(&coor), "{:?} {:?} {:?}", lattice, coor, neighbor);
+                }
+            }
+        }
+    }
+}
@@ -1,57 +1,205 @@
-use common::{AllTime, Month};
+use common::{AllTime, Target};
 use serde::{Deserialize, Serialize};
-use serde_wasm_bindgen;
 use wasm_bindgen::prelude::*;
 
-use crate::common::{Coordinate, WeekDay};
+use crate::common::{Coordinate, CustomBoard, PlacedPiece, Solution, Tile};
+use crate::drawing::Style;
+use crate::modified_board::ModifiedBoard;
+use crate::polyomino::Polyomino;
+use crate::schema::{PieceRecord, SolutionRecord};
+use crate::svg::SvgRenderer;
 
 mod active_board;
+mod active_box;
 mod common;
 mod custom_boards;
+mod drawing;
+mod exact_cover;
+mod lattice;
+mod modified_board;
+mod polycube;
+mod polyomino;
+mod schema;
 mod solver;
+mod svg;
 mod tile_helper;
 
-#[allow(dead_code)]
-pub const CUSTOM_BOARDS: [&dyn common::CustomBoard; 4] = [
-    &crate::custom_boards::nova_scotia::NovaScotiaCalendarBoard {},
-    &crate::custom_boards::gmdm::GMDoMBoard {},
-    &crate::custom_boards::gmdmwd::GMDoMWDBoard {},
-    &crate::custom_boards::gt::GTBoard {},
-];
+pub use custom_boards::{CUSTOM_BOARDS, MESSAGE_BOARDS};
+
+// one piece of a solution, the same as the command line's json output has (see schema.rs for the fields)
+pub type JsTile = PieceRecord;
+
+// pieces are given as ascii art ("##/##/#."), a list of coordinates ("0,0 1,0 2,0") or a polyomino name ("P-pentomino")
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct JsPieceOverrides {
+    #[serde(default)]
+    pub missing: Vec<usize>,
+    #[serde(default)]
+    pub replace: Vec<JsPieceReplacement>,
+    #[serde(default)]
+    pub add: Vec<String>,
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct JsTile {
-    pub coordinates: Vec<Coordinate>,
+pub struct JsPieceReplacement {
+    pub index: usize,
+    pub piece: String,
 }
 
+// date is "YYYY-MM-DD"; the weekday is worked out from it, and impossible dates (eg. "2026-02-30") throw
 #[wasm_bindgen]
-pub fn solve(month: u32, day: u32, week_day: u32, custom_board: &str) -> Vec<JsValue> {
-    let month = Month::from_u32(month).unwrap();
-    let week_day = WeekDay::from_u32(week_day).unwrap();
-    let all_time = AllTime::new(month, day, week_day);
+pub fn solve(date: &str, custom_board: &str) -> Result<Vec<JsValue>, JsError> {
+    let all_time = AllTime::from_iso(date)?;
     let board = match CUSTOM_BOARDS.iter().find(|b| b.name() == custom_board) {
         Some(b) => b,
-        None => return vec![],
+        None => return Ok(vec![]),
     };
 
-    match solver::run(&all_time, *board) {
-        Ok(solution) => {
-            let mut js_tiles: Vec<JsValue> = Vec::new();
-            for tile in solution {
-                let js_tile = JsTile { coordinates: tile };
-                js_tiles.push(serde_wasm_bindgen::to_value(&js_tile).unwrap());
-            }
-            return js_tiles;
+    Ok(to_js_tiles(solver::run(&Target::Date(all_time), *board)))
+}
+
+// same as solve, but with some of the board's pieces taken away, swapped out or added (see JsPieceOverrides);
+// a piece index that isn't on the board, a piece that can't be read, or pieces that don't fit the board throw
+#[wasm_bindgen]
+pub fn solve_with_overrides(date: &str, custom_board: &str, overrides: JsValue) -> Result<Vec<JsValue>, JsError> {
+    let all_time = AllTime::from_iso(date)?;
+    let board = match CUSTOM_BOARDS.iter().find(|b| b.name() == custom_board) {
+        Some(b) => *b,
+        None => return Ok(vec![]),
+    };
+    let overrides: JsPieceOverrides = serde_wasm_bindgen::from_value(overrides)?;
+
+    let piece_count = board.pieces().len();
+    let check_index = |index: usize| -> Result<usize, JsError> {
+        match index < piece_count {
+            true => Ok(index),
+            false => Err(JsError::new(&format!("invalid piece index: {}", index))),
         }
-        Err(_) => return vec![],
+    };
+    let parse = |piece: &str| -> Result<Tile, JsError> {
+        Polyomino::parse_piece(piece, board.lattice()).ok_or_else(|| JsError::new(&format!("invalid piece: {}", piece)))
+    };
+    let mut modified = ModifiedBoard::new(board);
+    for index in overrides.missing {
+        modified = modified.without_piece(check_index(index)?);
+    }
+    for replacement in overrides.replace {
+        modified = modified.replacing_piece(check_index(replacement.index)?, parse(&replacement.piece)?);
+    }
+    for piece in overrides.add {
+        modified = modified.with_extra_piece(parse(&piece)?);
+    }
+
+    match solver::run(&Target::Date(all_time), &modified) {
+        Err(solver::AREA_MISMATCH) => Err(JsError::new("the pieces don't cover the cells left to cover")),
+        result => Ok(to_js_tiles(result)),
+    }
+}
+
+// leave the cells with these labels uncovered instead of a date, eg. ["Jan", "15"], or ["G", "A", "M", "E"]
+// on a message board
+#[wasm_bindgen]
+pub fn solve_labels(labels: Vec<String>, custom_board: &str) -> Vec<JsValue> {
+    let board = match find_board(custom_board) {
+        Some(b) => b,
+        None => return vec![],
+    };
+
+    to_js_tiles(solver::run(&Target::Labels(labels), board))
+}
+
+fn to_js_tiles(result: Result<Solution, u32>) -> Vec<JsValue> {
+    match result {
+        Ok(solution) => solution
+            .pieces
+            .iter()
+            .enumerate()
+            .map(|(id, placed)| serde_wasm_bindgen::to_value(&JsTile::new(id, placed)).unwrap())
+            .collect(),
+        Err(_) => vec![],
     }
 }
 
+// the whole solution for a date, as a SolutionRecord (see schema.rs); null when there is none
+#[wasm_bindgen]
+pub fn solution_record(date: &str, custom_board: &str) -> Result<JsValue, JsError> {
+    let target = Target::Date(AllTime::from_iso(date)?);
+    let board = match CUSTOM_BOARDS.iter().find(|b| b.name() == custom_board) {
+        Some(b) => *b,
+        None => return Ok(JsValue::NULL),
+    };
+
+    match solver::run(&target, board) {
+        Ok(solution) => Ok(serde_wasm_bindgen::to_value(&SolutionRecord::new(board, &target, &solution))?),
+        Err(_) => Ok(JsValue::NULL),
+    }
+}
+
+// a board the page can pick, and how it's solved: "calendar" boards with solve (a date), "message"
+// boards with solve_labels (one label per letter)
+#[derive(Debug, Clone, Serialize)]
+pub struct JsBoardOption {
+    pub name: String,
+    pub kind: &'static str,
+}
+
 #[wasm_bindgen]
 pub fn get_board_options() -> JsValue {
     let mut boards = Vec::new();
-    for custom_board in CUSTOM_BOARDS {
-        boards.push(custom_board.name());
+    for (kind, custom_boards) in [("calendar", &CUSTOM_BOARDS[..]), ("message", &MESSAGE_BOARDS[..])] {
+        for custom_board in custom_boards {
+            boards.push(JsBoardOption {
+                name: custom_board.name(),
+                kind,
+            });
+        }
     }
     serde_wasm_bindgen::to_value(&boards).unwrap()
 }
+
+fn find_board(name: &str) -> Option<&'static dyn CustomBoard> {
+    CUSTOM_BOARDS.iter().chain(MESSAGE_BOARDS.iter()).find(|b| b.name() == name).copied()
+}
+
+// the board with every cell labelled, as an SVG image
+#[wasm_bindgen]
+pub fn board_svg(custom_board: &str) -> String {
+    match find_board(custom_board) {
+        Some(board) => SvgRenderer::new(Style::new()).board(board),
+        None => String::new(),
+    }
+}
+
+// draws the tiles solve returned, leaving off the pieces at the hidden indices; the cells no tile
+// covers are shaded as the target. Pieces are coloured from colors by piece index (empty for the defaults)
+#[wasm_bindgen]
+pub fn solution_svg(
+    custom_board: &str,
+    tiles: Vec<JsValue>,
+    hidden: Vec<usize>,
+    colors: Vec<String>,
+) -> Result<String, JsError> {
+    let board = match find_board(custom_board) {
+        Some(b) => b,
+        None => return Ok(String::new()),
+    };
+
+    let mut pieces: Vec<PlacedPiece> = Vec::new();
+    for tile in tiles {
+        let js_tile: JsTile = serde_wasm_bindgen::from_value(tile)?;
+        pieces.push(js_tile.placed());
+    }
+    let target: Vec<Coordinate> = board
+        .coors()
+        .into_iter()
+        .filter(|coor| !pieces.iter().any(|placed| placed.tile.contains(coor)))
+        .collect();
+    let solution = Solution {
+        pieces,
+        target,
+        encoding: 0,
+        gaps: vec![],
+    };
+
+    Ok(SvgRenderer::new(Style::new().with_colors(colors).with_hidden(hidden)).solution(board, &solution))
+}
@@ -1,61 +1,1148 @@
+use std::io::Write;
+
+use serde::Serialize;
+
 use crate::{
-    common::{AllTime, CustomBoard, Tile},
+    board_file::FileBoard,
+    book::PuzzleBook,
+    common::{AllTime, Coordinate, CustomBoard, Target, Tile, Zone},
+    custom_boards::{CUSTOM_BOARDS, CUSTOM_POLYCUBES, MESSAGE_BOARDS},
+    drawing::{Style, hex_rgb},
+    laser::LaserCut,
+    lattice::Lattice,
+    modified_board::ModifiedBoard,
+    polycube::{PlacedCubes, PolycubePuzzle},
+    polyomino::Polyomino,
+    schema::SolutionRecord,
+    solver::{Backend, Search},
+    stl::{Part, StlModel},
+    svg::SvgRenderer,
+    terminal::TerminalRenderer,
+    tikz::TikzRenderer,
 };
 
 mod active_board;
+mod active_box;
+mod board_file;
+mod book;
 mod common;
 mod custom_boards;
+mod drawing;
+mod exact_cover;
+mod laser;
+mod lattice;
+mod modified_board;
+mod polycube;
+mod polyomino;
+mod schema;
+#[cfg(feature = "png")]
+mod png;
 mod tile_helper;
 mod solver;
+mod stl;
+mod svg;
+mod terminal;
+mod tikz;
 
-fn main() -> Result<(), u32> {
-    let now = AllTime::must_get_current_time();
-    let board = custom_boards::nova_scotia::NovaScotiaCalendarBoard {};
-    let hide_tiles = 5;
+const INVALID_ARGUMENTS: u32 = 2;
+const NO_CURRENT_TIME: u32 = 6;
+const OUTPUT_FAILED: u32 = 8;
+
+// print! and println!, but to a locked stdout, and stopping quietly once nothing is reading (see written)
+macro_rules! out {
+    ($($arg:tt)*) => {
+        written(write!(std::io::stdout().lock(), $($arg)*))
+    };
+}
+
+macro_rules! outln {
+    ($($arg:tt)*) => {
+        written(writeln!(std::io::stdout().lock(), $($arg)*))
+    };
+}
+
+// what each command that writes solutions or boards can write
+const SOLUTION_FORMATS: &[Format] = &[
+    Format::Text,
+    Format::Json,
+    Format::Ndjson,
+    Format::Csv,
+    Format::Svg,
+    Format::Tikz,
+    #[cfg(feature = "png")]
+    Format::Png,
+];
+const STREAM_FORMATS: &[Format] = &[Format::Text, Format::Json, Format::Ndjson, Format::Csv];
+const BOARD_FORMATS: &[Format] = &[
+    Format::Text,
+    Format::Json,
+    Format::Svg,
+    Format::Tikz,
+    #[cfg(feature = "png")]
+    Format::Png,
+];
+
+// how results are written to stdout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Text,
+    Json,
+    Ndjson, // one json object per line, for streams of solutions
+    Csv,    // one row per piece
+    // only for the commands that draw a board (solve and board)
+    Svg,
+    Tikz, // a tikzpicture, to \input into a LaTeX document
+    Dxf,  // only for cut
+    // only for stl
+    Stl,
+    StlBinary,
+    #[cfg(feature = "png")]
+    Png,
+}
 
-    println!("Today is: {:?}", now);
-    println!("Solving for: {:?}", board.point_in_time(&now));
+impl Format {
+    fn from_name(name: &str) -> Option<Format> {
+        match name.to_lowercase().as_str() {
+            "text" => Some(Format::Text),
+            "json" => Some(Format::Json),
+            "ndjson" => Some(Format::Ndjson),
+            "csv" => Some(Format::Csv),
+            "svg" => Some(Format::Svg),
+            "tikz" => Some(Format::Tikz),
+            "dxf" => Some(Format::Dxf),
+            "stl" => Some(Format::Stl),
+            "stl-binary" => Some(Format::StlBinary),
+            #[cfg(feature = "png")]
+            "png" => Some(Format::Png),
+            _ => None,
+        }
+    }
+
+    // as it's given to --format
+    fn name(&self) -> &'static str {
+        match self {
+            Format::Text => "text",
+            Format::Json => "json",
+            Format::Ndjson => "ndjson",
+            Format::Csv => "csv",
+            Format::Svg => "svg",
+            Format::Tikz => "tikz",
+            Format::Dxf => "dxf",
+            Format::Stl => "stl",
+            Format::StlBinary => "stl-binary",
+            #[cfg(feature = "png")]
+            Format::Png => "png",
+        }
+    }
+}
+
+// everything that can be passed on the command line; not every command uses every option
+struct Options {
+    board: &'static dyn CustomBoard,
+    puzzle: Option<&'static dyn PolycubePuzzle>, // count or pack a 3d puzzle instead of a board
+    zone: Zone,        // the clock used for today's date
+    year: Option<i32>, // this year, by default
+    missing: Vec<usize>,
+    replaced: Vec<(usize, Tile)>,
+    extra: Vec<Tile>,
+    labels: Option<Vec<String>>, // leave these cells uncovered instead of today's date
+    date: Option<AllTime>,       // solve for this day instead of today
+    limit: Option<usize>,        // the most solutions to list; all of them, by default
+    hide: usize,                 // pieces left out of the printed solution, so it can still be a puzzle
+    seed: Option<u64>,           // makes the solution (and the hidden pieces) the same from one run to the next
+    backend: Backend,
+    format: Format,
+    all_labels: bool,    // label every cell that isn't under a shown piece, for a hint sheet
+    colors: Vec<String>, // piece colours for text, svg and png output; empty for the defaults
+    out: Option<String>, // the directory the book is written to
+    cell_size: Option<f64>, // in millimetres, for cut and stl
+    kerf: Option<f64>,      // in millimetres, for cut
+    height: Option<f64>,    // of the pieces, in millimetres, for stl
+    clearance: Option<f64>, // between the pieces, in millimetres, for stl
+    part: Part,             // what stl makes
+}
+
+impl Options {
+    fn parse(args: &[String]) -> Result<Options, u32> {
+        let mut options = Options {
+            board: CUSTOM_BOARDS[0],
+            puzzle: None,
+            zone: Zone::Local,
+            year: None,
+            missing: vec![],
+            replaced: vec![],
+            extra: vec![],
+            labels: None,
+            date: None,
+            limit: None,
+            hide: 5,
+            seed: None,
+            backend: Backend::Backtracking,
+            format: Format::Text,
+            all_labels: false,
+            colors: vec![],
+            out: None,
+            cell_size: None,
+            kerf: None,
+            height: None,
+            clearance: None,
+            part: Part::Both,
+        };
+
+        let mut missing: Vec<String> = Vec::new();
+        let mut replaced: Vec<String> = Vec::new();
+        let mut extra: Vec<String> = Vec::new();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let value = match args.next() {
+                Some(value) => value,
+                None => {
+                    eprintln!("missing value for {}", arg);
+                    return Err(INVALID_ARGUMENTS);
+                }
+            };
+            match arg.as_str() {
+                "--board" => match find_board(value) {
+                    Some(b) => options.board = b,
+                    None => {
+                        eprintln!("unknown board: {} (see list-boards)", value);
+                        return Err(INVALID_ARGUMENTS);
+                    }
+                },
+                // the board has to outlive every ModifiedBoard made from it, which is the rest of the run
+                "--board-file" => match FileBoard::load(value) {
+                    Ok(b) => options.board = Box::leak(Box::new(b)),
+                    Err(e) => {
+                        eprintln!("{}", e);
+                        return Err(INVALID_ARGUMENTS);
+                    }
+                },
+                "--puzzle" => match find_polycube_puzzle(value) {
+                    Some(p) => options.puzzle = Some(p),
+                    None => {
+                        eprintln!("unknown puzzle: {} (see list-boards)", value);
+                        return Err(INVALID_ARGUMENTS);
+                    }
+                },
+                "--year" => match value.parse() {
+                    Ok(y) => options.year = Some(y),
+                    Err(_) => {
+                        eprintln!("invalid year: {}", value);
+                        return Err(INVALID_ARGUMENTS);
+                    }
+                },
+                "--tz" => match Zone::parse(value) {
+                    Ok(zone) => options.zone = zone,
+                    Err(e) => {
+                        eprintln!("{}", e);
+                        return Err(INVALID_ARGUMENTS);
+                    }
+                },
+                "--date" => match AllTime::from_iso(value) {
+                    Ok(date) => options.date = Some(date),
+                    Err(e) => {
+                        eprintln!("invalid date: {}", e);
+                        return Err(INVALID_ARGUMENTS);
+                    }
+                },
+                "--hide" => match value.parse() {
+                    Ok(hide) => options.hide = hide,
+                    Err(_) => {
+                        eprintln!("invalid number of pieces to hide: {}", value);
+                        return Err(INVALID_ARGUMENTS);
+                    }
+                },
+                "--limit" => match value.parse() {
+                    Ok(limit) => options.limit = Some(limit),
+                    Err(_) => {
+                        eprintln!("invalid limit: {}", value);
+                        return Err(INVALID_ARGUMENTS);
+                    }
+                },
+                "--seed" => match value.parse() {
+                    Ok(seed) => options.seed = Some(seed),
+                    Err(_) => {
+                        eprintln!("invalid seed: {}", value);
+                        return Err(INVALID_ARGUMENTS);
+                    }
+                },
+                "--solver" => match Backend::from_name(value) {
+                    Some(backend) => options.backend = backend,
+                    None => {
+                        eprintln!("unknown solver: {} (expected backtracking or exact-cover)", value);
+                        return Err(INVALID_ARGUMENTS);
+                    }
+                },
+                "--format" => match Format::from_name(value) {
+                    Some(format) => options.format = format,
+                    None => {
+                        eprintln!(
+                            "unknown format: {} (expected text, json, ndjson, csv, svg, tikz, dxf, stl, stl-binary, or png when built with the png feature)",
+                            value
+                        );
+                        return Err(INVALID_ARGUMENTS);
+                    }
+                },
+                "--show-labels" => match value.as_str() {
+                    "target" => options.all_labels = false,
+                    "all" => options.all_labels = true,
+                    _ => {
+                        eprintln!("expected target or all for --show-labels, got: {}", value);
+                        return Err(INVALID_ARGUMENTS);
+                    }
+                },
+                "--colors" => options.colors = value.split(',').map(|c| c.trim().to_string()).collect(),
+                "--out" => options.out = Some(value.clone()),
+                "--cell-size" => options.cell_size = Some(parse_length(arg, value, false)?),
+                "--kerf" => options.kerf = Some(parse_length(arg, value, true)?),
+                "--height" => options.height = Some(parse_length(arg, value, false)?),
+                "--clearance" => options.clearance = Some(parse_length(arg, value, true)?),
+                "--part" => match Part::from_name(value) {
+                    Some(part) => options.part = part,
+                    None => {
+                        eprintln!("invalid part: {} (expected tray, pieces or both)", value);
+                        return Err(INVALID_ARGUMENTS);
+                    }
+                },
+                // these are checked (and pieces parsed for the board's lattice) once the board is known,
+                // since --board can come after them
+                "--missing" => missing.push(value.clone()),
+                "--replace" => replaced.push(value.clone()),
+                "--add" => extra.push(value.clone()),
+                "--labels" => options.labels = Some(value.split(',').map(|l| l.trim().to_string()).collect()),
+                "--message" => options.labels = Some(value.chars().map(|ch| ch.to_string()).collect()),
+                _ => {
+                    eprintln!("unknown option: {}", arg);
+                    print_usage();
+                    return Err(INVALID_ARGUMENTS);
+                }
+            }
+        }
+
+        for index in missing.iter() {
+            let index = options.piece_index(index)?;
+            options.missing.push(index);
+        }
+        for value in replaced.iter() {
+            // <piece index>=<piece>
+            let (index, piece) = match value.split_once('=') {
+                Some(parts) => parts,
+                None => {
+                    eprintln!("expected <piece index>=<piece>, got: {}", value);
+                    return Err(INVALID_ARGUMENTS);
+                }
+            };
+            let index = options.piece_index(index)?;
+            options.replaced.push((index, parse_piece(piece, options.board.lattice())?));
+        }
+        for value in extra.iter() {
+            let tile = parse_piece(value, options.board.lattice())?;
+            options.extra.push(tile);
+        }
+        Ok(options)
+    }
+
+    // not every command can write every format
+    fn accepting(self, command: &str, formats: &[Format]) -> Result<Options, u32> {
+        if !formats.contains(&self.format) {
+            let names: Vec<&str> = formats.iter().map(|format| format.name()).collect();
+            eprintln!("{} can't write {} (it writes {})", command, self.format.name(), names.join(", "));
+            return Err(INVALID_ARGUMENTS);
+        }
+        Ok(self)
+    }
+
+    // for the commands that don't write text; without a --format, they write this instead
+    fn defaulting_to(mut self, format: Format) -> Options {
+        if self.format == Format::Text {
+            self.format = format;
+        }
+        self
+    }
+
+    fn piece_index(&self, value: &str) -> Result<usize, u32> {
+        match value.trim().parse() {
+            Ok(index) if index < self.board.pieces().len() => Ok(index),
+            _ => {
+                eprintln!("invalid piece index: {}", value);
+                Err(INVALID_ARGUMENTS)
+            }
+        }
+    }
+
+    fn today(&self) -> Result<AllTime, u32> {
+        AllTime::current_time(&self.zone).map_err(|e| {
+            eprintln!("{}", e);
+            NO_CURRENT_TIME
+        })
+    }
+
+    // the labels, the date, or today, in that order
+    fn target(&self) -> Result<Target, u32> {
+        match (&self.labels, &self.date) {
+            (Some(labels), _) => Ok(Target::Labels(labels.clone())),
+            (None, Some(date)) => Ok(Target::Date(date.clone())),
+            (None, None) => {
+                let now = self.today()?;
+                if self.format == Format::Text {
+                    outln!("Today is: {:?}", now);
+                }
+                Ok(Target::Date(now))
+            }
+        }
+    }
+
+    // the piece colours, with the pieces at the hidden indices left off
+    fn style(&self, hidden: Vec<usize>) -> Style {
+        Style::new().with_colors(self.colors.clone()).with_hidden(hidden)
+    }
+
+    // png, tikz and the terminal have no colour names, so every colour has to be written out
+    fn hex_style(&self, hidden: Vec<usize>) -> Result<Style, u32> {
+        if let Some(color) = self.colors.iter().find(|color| hex_rgb(color).is_none()) {
+            eprintln!("invalid colour: {} (expected #RRGGBB or #RGB; only svg takes colour names)", color);
+            return Err(INVALID_ARGUMENTS);
+        }
+        Ok(self.style(hidden))
+    }
 
-    match solver::run(&now, &board) {
-        Ok(solution) => {
-            println!("found: ");
-            print_solution(&solution, hide_tiles);
+    fn terminal_renderer(&self, hidden: Vec<usize>) -> Result<TerminalRenderer, u32> {
+        Ok(TerminalRenderer::new(self.hex_style(hidden)?).with_all_labels(self.all_labels))
+    }
+
+    fn search(&self) -> Search {
+        Search::new().with_backend(self.backend).with_seed(self.seed)
+    }
+
+    // the chosen board, with pieces taken away / swapped / added as requested
+    fn modified_board(&self) -> ModifiedBoard<'static> {
+        let mut board = ModifiedBoard::new(self.board);
+        for index in self.missing.iter() {
+            board = board.without_piece(*index);
+        }
+        for (index, tile) in self.replaced.iter() {
+            board = board.replacing_piece(*index, tile.clone());
+        }
+        for tile in self.extra.iter() {
+            board = board.with_extra_piece(tile.clone());
+        }
+        board
+    }
+}
+
+fn main() -> Result<(), u32> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.first().map(|arg| arg.as_str()) {
+        Some("solve") => solve(&Options::parse(&args[1..])?.accepting("solve", SOLUTION_FORMATS)?),
+        Some("solutions") => solutions(&Options::parse(&args[1..])?.accepting("solutions", STREAM_FORMATS)?),
+        Some("count") => count(&Options::parse(&args[1..])?.accepting("count", &[Format::Text, Format::Json])?),
+        Some("report") => report(&Options::parse(&args[1..])?.accepting("report", &[Format::Text, Format::Json])?),
+        Some("list-boards") => {
+            list_boards(&Options::parse(&args[1..])?.accepting("list-boards", &[Format::Text, Format::Json])?)
+        }
+        Some("pack") => pack(&Options::parse(&args[1..])?.accepting("pack", &[Format::Text, Format::Json])?),
+        Some("book") => book(&Options::parse(&args[1..])?.accepting("book", &[Format::Text, Format::Json])?),
+        Some("cut") => cut(&Options::parse(&args[1..])?
+            .defaulting_to(Format::Svg)
+            .accepting("cut", &[Format::Svg, Format::Dxf])?),
+        Some("stl") => stl(&Options::parse(&args[1..])?
+            .defaulting_to(Format::Stl)
+            .accepting("stl", &[Format::Stl, Format::StlBinary])?),
+        Some("board") => board(&Options::parse(&args[1..])?.accepting("board", BOARD_FORMATS)?),
+        Some("help") | Some("--help") | Some("-h") => {
+            print_usage();
             Ok(())
         }
-        Err(e) => Err(e),
+        Some(other) => {
+            eprintln!("unknown command: {}", other);
+            print_usage();
+            Err(INVALID_ARGUMENTS)
+        }
+        None => solve(&Options::parse(&[])?),
     }
 }
 
-fn print_solution(tiles: &Vec<Tile>, hide_tiles: usize) {
-    const TILE_MARKERS: [char; 16] = [
-        'I', 'O', 'B', 'S', 'X', '2', 'N', 'V', 'Y', '7', 'J', 'T', '9', 'W', 'K', 'L',
-    ];
+fn print_usage() {
+    outln!("usage:");
+    outln!("  puzzle-a-day solve [board options] [--date <YYYY-MM-DD> | --labels <label>,<label>,... | --message <text>]");
+    outln!("                     [--hide <count>] [--show-labels target|all] [--seed <number>] [--solver <backend>]");
+    outln!("                     [--format <format>]");
+    outln!("      solve the board for today or the given date, or leaving the labelled cells (eg. \"Jan,15,Mon\")");
+    outln!("      uncovered; --message uses one label per character, for message boards. --hide leaves that many");
+    outln!("      pieces (5 by default) out of the printed solution. --show-labels all labels every cell those pieces");
+    outln!("      would cover too (on square boards), so the printout can be used as a hint sheet");
+    outln!("  puzzle-a-day solutions [board options] [--date <YYYY-MM-DD> | --labels ... | --message ...] [--limit <count>]");
+    outln!("                     [--seed <number>] [--solver <backend>] [--format text|json|ndjson|csv]");
+    outln!("      list every solution for the target (or the first <count>) as they're found; see schema.rs");
+    outln!("      for the fields of json, ndjson and csv");
+    outln!("  puzzle-a-day count [board options] [--date <YYYY-MM-DD> | --labels ... | --message ...] [--puzzle <name>]");
+    outln!("                     [--solver <backend>] [--format <format>]");
+    outln!("      count every solution for the target (or every way to pack a 3d puzzle)");
+    outln!("  puzzle-a-day report [board options] [--year <year>] [--seed <number>] [--solver <backend>] [--format <format>]");
+    outln!("      check which dates of the year can still be solved with a modified set of pieces, and which cells");
+    outln!("      every solution leaves uncovered when the pieces can't cover the board");
+    outln!("  puzzle-a-day book [board options] [--year <year>] [--out <directory>] [--hide <count>] [--seed <number>]");
+    outln!("                     [--solver <backend>] [--colors <color>,<color>,...] [--format <format>]");
+    outln!("      write a printable puzzle book for every day of the year (this year by default) into the directory");
+    outln!("      (book-<year> by default): the empty board, a hint with all but --hide pieces put down, and the");
+    outln!("      answers at the back, as svg pages and an index.html to print them from");
+    outln!("  puzzle-a-day board [board options] [--format <format>] [--colors <color>,<color>,...]");
+    outln!("      show the board with every cell labelled");
+    outln!("  puzzle-a-day cut [board options] [--cell-size <mm>] [--kerf <mm>] [--format svg|dxf]");
+    outln!("      the board and its pieces as outlines for a laser cutter, in millimetres: a frame with the board");
+    outln!("      cut out of it, a base plate with the labels engraved, and every piece. --cell-size is 20 and");
+    outln!("      --kerf 0.15 by default; cut lines are moved by half the kerf so the parts come out true to size");
+    outln!("  puzzle-a-day stl [board options] [--part tray|pieces|both] [--cell-size <mm>] [--height <mm>]");
+    outln!("                     [--clearance <mm>] [--format stl|stl-binary]");
+    outln!("      the pieces (--height thick, 6 by default) and a tray for them, with the labels engraved in it,");
+    outln!("      to 3d print. --cell-size is 20 by default; the pieces are --clearance (0.3 by default) apart");
+    outln!("      in the tray, and they're laid out next to it unless --part picks just one");
+    outln!("  puzzle-a-day list-boards [--format <format>]");
+    outln!("      the built-in boards and 3d puzzles");
+    outln!("  puzzle-a-day pack [--puzzle <name>] [--seed <number>] [--solver <backend>] [--format <format>]");
+    outln!("      fill a 3d puzzle ({} by default) with its pieces, and print it layer by layer", CUSTOM_POLYCUBES[0].name());
+    outln!("board options:");
+    outln!("  --board <name>                      one of the built-in boards ({} by default)", CUSTOM_BOARDS[0].name());
+    outln!("  --board-file <path>                 a board described in a text file (see board_file.rs)");
+    outln!("  --tz <zone>                         the clock for today (and this year): utc, local (the default),");
+    outln!("                                      an offset like +05:30, or an IANA name like America/Halifax");
+    outln!("                                      when built with the tz feature");
+    outln!("  --missing <piece index>             leave out one copy of a piece");
+    outln!("  --replace <piece index>=<piece>     use a different shape instead of a piece");
+    outln!("  --add <piece>                       add an extra piece");
+    outln!("  pieces are ascii art with rows split by '/' (eg. \"##/##/#.\"), coordinates (eg. \"0,0 1,0 2,0\")");
+    outln!("  or polyomino names (eg. \"P-pentomino\")");
+    outln!("other options:");
+    outln!("  --seed <number>                     the same seed gives the same solution every time");
+    outln!("  --solver backtracking|exact-cover   backtracking (the default) places one piece at a time; exact-cover");
+    outln!("                                      tries the most constrained cell or piece first, but can't leave gaps");
+    outln!("                                      or skip optional pieces");
+    outln!("  --format <format>                   text, json, ndjson or csv (solve and solutions only), svg, tikz or png");
+    outln!("                                      (dxf for cut, stl or stl-binary for stl).");
+    outln!("                                      svg, tikz and png draw the board (solve and board only); the hidden");
+    outln!("                                      pieces are left off, so --hide 99 draws the empty puzzle. tikz is a");
+    outln!("                                      tikzpicture to \\input into LaTeX. png needs the png feature, and is");
+    outln!("                                      written to stdout");
+    outln!("  --colors <color>,<color>,...        piece colours, eg. \"#FF5733,#3357FF\" (svg also takes names like teal);");
+    outln!("                                      text is only coloured when written to a terminal");
+}
+
+// a positive number of millimetres (or zero, when that makes sense)
+fn parse_length(arg: &str, value: &str, zero: bool) -> Result<f64, u32> {
+    match value.parse::<f64>() {
+        Ok(length) if length.is_finite() && (length > 0.0 || (zero && length == 0.0)) => Ok(length),
+        _ => {
+            eprintln!("invalid value for {}: {}", arg, value);
+            Err(INVALID_ARGUMENTS)
+        }
+    }
+}
+
+// the kerf and the clearance move the edges of every cell inwards; once that reaches the middle of the
+// cell, there's nothing left of it
+fn check_inset(arg: &str, inset: f64, cell_size: f64, lattice: Lattice) -> Result<(), u32> {
+    let limit = lattice.inscribed_radius() * cell_size;
+    if inset >= limit {
+        eprintln!("{} has to be less than {:.3}mm for {}mm cells on this board", arg, limit, cell_size);
+        return Err(INVALID_ARGUMENTS);
+    }
+    Ok(())
+}
+
+fn parse_piece(value: &str, lattice: Lattice) -> Result<Tile, u32> {
+    match Polyomino::parse_piece(value, lattice) {
+        Some(tile) => Ok(tile),
+        None => {
+            eprintln!("invalid piece: {}", value);
+            Err(INVALID_ARGUMENTS)
+        }
+    }
+}
+
+fn print_json<T: Serialize>(value: &T) {
+    outln!("{}", serde_json::to_string_pretty(value).expect("output is always serializable"));
+}
+
+// json is pretty-printed, ndjson is one line per solution, and csv is one row per piece (after a
+// header, when this is the first solution written)
+fn print_record(record: &SolutionRecord, format: Format, first: bool) {
+    match format {
+        Format::Json => print_json(record),
+        Format::Ndjson => outln!("{}", serde_json::to_string(record).expect("output is always serializable")),
+        Format::Csv => {
+            if first {
+                outln!("{}", schema::csv_header());
+            }
+            for row in record.csv_rows() {
+                outln!("{}", row);
+            }
+        }
+        _ => unreachable!("only called for json, ndjson and csv"),
+    }
+}
+
+fn solve(options: &Options) -> Result<(), u32> {
+    let board = options.modified_board();
+    let target = options.target()?;
+    let alternatives = board.target_alternatives(&target);
+    if let (Format::Text, Some(cells)) = (options.format, alternatives.first()) {
+        let mut cells: Vec<Coordinate> = cells.iter().copied().collect();
+        cells.sort_by_key(|coor| (coor.y, coor.x));
+        outln!("Solving for: {}", describe_cells(&board, &cells));
+    }
+
+    let search = options.search();
+    let solution = match search.run(&target, &board) {
+        Ok(solution) => solution,
+        Err(e) => return Err(report_error(e, &target, &board)),
+    };
+    let hidden = solver::hidden_pieces(solution.pieces.len(), options.hide, &mut search.rng());
+
+    match options.format {
+        Format::Text => {
+            // boards with repeated labels can show the same target in more than one way
+            if alternatives.len() > 1 {
+                outln!(
+                    "leaving {} uncovered (encoding {} of {})",
+                    describe_cells(&board, &solution.target),
+                    solution.encoding + 1,
+                    alternatives.len()
+                );
+            }
+            outln!("found: ");
+            out!("{}", options.terminal_renderer(hidden)?.solution(&board, &solution));
+        }
+        Format::Svg => out!("{}", SvgRenderer::new(options.style(hidden)).solution(&board, &solution)),
+        Format::Tikz => out!("{}", TikzRenderer::new(options.hex_style(hidden)?).solution(&board, &solution)),
+        #[cfg(feature = "png")]
+        Format::Png => {
+            let renderer = png::PngRenderer::new(options.hex_style(hidden)?);
+            write_png(renderer.solution(&board, &solution))?;
+        }
+        Format::Json | Format::Ndjson | Format::Csv => {
+            let record = SolutionRecord::new(&board, &target, &solution)
+                .with_seed(options.seed)
+                .with_hidden(hidden);
+            print_record(&record, options.format, true);
+        }
+        _ => unreachable!("rejected by Options::accepting"),
+    }
+    Ok(())
+}
+
+// every solution for the target (or the first --limit of them), one after the other as they're found
+fn solutions(options: &Options) -> Result<(), u32> {
+    let board = options.modified_board();
+    let target = options.target()?;
+    let limit = options.limit.unwrap_or(usize::MAX);
+    if limit == 0 {
+        return Ok(());
+    }
+
+    // json is a single array, so it's only written once every solution is in
+    let mut records: Vec<SolutionRecord> = Vec::new();
+    let mut failed: Option<u32> = None;
+    let mut written = 0;
+    let result = options.search().each(&target, &board, &mut |solution| {
+        let index = written;
+        written += 1;
+        let record = SolutionRecord::new(&board, &target, &solution)
+            .with_seed(options.seed)
+            .with_index(index);
+        match options.format {
+            Format::Text => {
+                outln!("solution {}:", index + 1);
+                match options.terminal_renderer(vec![]) {
+                    Ok(renderer) => out!("{}", renderer.solution(&board, &solution)),
+                    Err(e) => {
+                        failed = Some(e);
+                        return false;
+                    }
+                }
+            }
+            Format::Json => records.push(record),
+            format => print_record(&record, format, index == 0),
+        }
+        written < limit
+    });
+    if let Some(e) = failed {
+        return Err(e);
+    }
+
+    let found = match result {
+        Ok(found) => found,
+        Err(e) => return Err(report_error(e, &target, &board)),
+    };
+    match options.format {
+        Format::Json => print_json(&records),
+        Format::Text if found == 0 => eprintln!("no solution found"),
+        _ => {}
+    }
+    if found == 0 {
+        return Err(solver::NO_SOLUTION_FOUND);
+    }
+    Ok(())
+}
+
+// explain why the board couldn't be solved, and pass the error on
+fn report_error(error: u32, target: &Target, board: &dyn CustomBoard) -> u32 {
+    match error {
+        solver::AREA_MISMATCH => print_area_mismatch(target, board),
+        solver::INVALID_TARGET => eprintln!("{} can't show {:?}", board.name(), target),
+        solver::NO_SOLUTION_FOUND => eprintln!("no solution found"),
+        solver::UNSUPPORTED_BY_BACKEND => {
+            eprintln!("the exact-cover solver can't leave gaps or skip pieces; try --solver backtracking")
+        }
+        _ => {}
+    }
+    error
+}
+
+fn count(options: &Options) -> Result<(), u32> {
+    let search = options.search();
+    let (name, solutions) = match options.puzzle {
+        Some(puzzle) => (puzzle.name(), search.count_packings(puzzle)?),
+        None => {
+            let board = options.modified_board();
+            let target = options.target()?;
+            if board.target_alternatives(&target).is_empty() {
+                return Err(report_error(solver::INVALID_TARGET, &target, &board));
+            }
+            match search.count(&target, &board) {
+                Ok(solutions) => (board.name(), solutions),
+                Err(e) => return Err(report_error(e, &target, &board)),
+            }
+        }
+    };
 
-    let max_x = tiles
+    match options.format {
+        Format::Text => outln!("{}: {} solutions", name, solutions),
+        Format::Json => {
+            #[derive(Serialize)]
+            struct Output {
+                board: String,
+                solutions: usize,
+            }
+            print_json(&Output { board: name, solutions });
+        }
+        _ => unreachable!("rejected by Options::accepting"),
+    }
+    Ok(())
+}
+
+// the labels of the cells (or their coordinates, for cells without one)
+fn describe_cells(board: &dyn CustomBoard, cells: &[Coordinate]) -> String {
+    let labels = board.labels();
+    let described: Vec<String> = cells
         .iter()
-        .flat_map(|tile| tile.iter().map(|coor| coor.x))
-        .max()
-        .unwrap_or(0);
-    let max_y = tiles
+        .map(|coor| match labels.get(coor) {
+            Some(label) => label.clone(),
+            None => format!("({}, {})", coor.x, coor.y),
+        })
+        .collect();
+    described.join(" ")
+}
+
+fn print_area_mismatch(target: &Target, board: &dyn CustomBoard) {
+    let area: usize = board.pieces().iter().map(|p| p.tile.len() * p.count).sum();
+    let target_size = board.target_alternatives(target).first().map(|coors| coors.len()).unwrap_or(0);
+    let open = board.coors().len() - target_size;
+    eprintln!(
+        "the pieces cover {} cells, but there are {} cells to cover",
+        area, open
+    );
+}
+
+// for every day of the year, try to solve the board with a modified set of pieces
+fn report(options: &Options) -> Result<(), u32> {
+    let board = options.modified_board();
+    let year = match options.year {
+        Some(year) => year,
+        None => options.today()?.year().unwrap_or_default(),
+    };
+    let text = options.format == Format::Text;
+
+    if text {
+        outln!("{}, {}", board.name(), year);
+        outln!("pieces:");
+        for (i, piece) in board.pieces().iter().enumerate() {
+            let cells: Vec<String> = piece.tile.iter().map(|c| format!("({}, {})", c.x, c.y)).collect();
+            match Polyomino::name(&piece.tile).filter(|_| board.lattice() == Lattice::Square) {
+                Some(name) => outln!("  {}: {} {}", i, name, cells.join(" ")),
+                None => outln!("  {}: {}", i, cells.join(" ")),
+            }
+        }
+    }
+
+    #[derive(Serialize)]
+    struct Day {
+        date: String,
+        status: &'static str,  // "solvable", "gaps", "invalid" (the board can't show it) or "no-solution"
+        gap_count: usize,      // cells left uncovered besides the date, when the pieces can't cover the whole board
+        gaps: Vec<Coordinate>, // the ones every solution has to leave uncovered; the rest can be anywhere
+    }
+
+    let search = options.search();
+    let mut results: Vec<Day> = Vec::new();
+    let days = AllTime::every_day_of(year);
+    for day in days.iter() {
+        let date = format!("{:?} {} ({:?})", day.month(), day.day_of_month(), day.week_day());
+        let target = Target::Date(day.clone());
+        let (status, gap_count, gaps) = match search.forced_gaps(&target, &board) {
+            Ok((solution, _)) if solution.gaps.is_empty() => {
+                if text {
+                    outln!("{}: solvable", date);
+                }
+                ("solvable", 0, vec![])
+            }
+            Ok((solution, forced)) => {
+                if text {
+                    let cells: Vec<String> = forced.iter().map(|g| format!("({}, {})", g.x, g.y)).collect();
+                    let must = match forced.len() {
+                        0 => "any of them can be covered".to_string(),
+                        _ => format!("{} must remain uncovered", cells.join(" ")),
+                    };
+                    outln!("{}: solvable, leaving {} cells uncovered; {}", date, solution.gaps.len(), must);
+                }
+                ("gaps", solution.gaps.len(), forced)
+            }
+            Err(solver::INVALID_TARGET) => {
+                if text {
+                    outln!("{}: can't be shown on this board", date);
+                }
+                ("invalid", 0, vec![])
+            }
+            Err(solver::NO_SOLUTION_FOUND) => {
+                if text {
+                    outln!("{}: no solution", date);
+                }
+                ("no-solution", 0, vec![])
+            }
+            Err(e) => return Err(report_error(e, &target, &board)),
+        };
+        results.push(Day {
+            date: day.iso_date().unwrap_or(date),
+            status,
+            gap_count,
+            gaps,
+        });
+    }
+
+    let solvable = results.iter().filter(|day| day.status == "solvable" || day.status == "gaps").count();
+    match options.format {
+        Format::Text => outln!("{} of {} dates can be solved", solvable, days.len()),
+        Format::Json => {
+            #[derive(Serialize)]
+            struct Output {
+                board: String,
+                year: i32,
+                solvable: usize,
+                days: Vec<Day>,
+            }
+            print_json(&Output {
+                board: board.name(),
+                year,
+                solvable,
+                days: results,
+            });
+        }
+        _ => unreachable!("rejected by Options::accepting"),
+    }
+
+    Ok(())
+}
+
+// every board and 3d puzzle that can be picked by name
+fn list_boards(options: &Options) -> Result<(), u32> {
+    #[derive(Serialize)]
+    struct Entry {
+        name: String,
+        kind: &'static str, // "calendar", "message" or "3d"
+        lattice: Option<String>,
+        cells: usize,
+        pieces: usize,
+    }
+
+    let mut entries: Vec<Entry> = Vec::new();
+    for (kind, boards) in [("calendar", &CUSTOM_BOARDS[..]), ("message", &MESSAGE_BOARDS[..])] {
+        for board in boards {
+            entries.push(Entry {
+                name: board.name(),
+                kind,
+                lattice: Some(format!("{:?}", board.lattice()).to_lowercase()),
+                cells: board.coors().len(),
+                pieces: board.pieces().iter().map(|p| p.count).sum(),
+            });
+        }
+    }
+    for puzzle in CUSTOM_POLYCUBES {
+        entries.push(Entry {
+            name: puzzle.name(),
+            kind: "3d",
+            lattice: None,
+            cells: puzzle.cells().len(),
+            pieces: puzzle.pieces().iter().map(|p| p.count).sum(),
+        });
+    }
+
+    match options.format {
+        Format::Text => {
+            for entry in entries {
+                let lattice = entry.lattice.map(|l| format!(", {}", l)).unwrap_or_default();
+                outln!("{} ({}{}): {} cells, {} pieces", entry.name, entry.kind, lattice, entry.cells, entry.pieces);
+            }
+        }
+        Format::Json => print_json(&entries),
+        _ => unreachable!("rejected by Options::accepting"),
+    }
+    Ok(())
+}
+
+// solve one of the 3d puzzles
+fn pack(options: &Options) -> Result<(), u32> {
+    let puzzle = options.puzzle.unwrap_or(CUSTOM_POLYCUBES[0]);
+    let solution = options.search().pack(puzzle)?;
+
+    match options.format {
+        Format::Text => {
+            outln!("{}", puzzle.name());
+            print_layers(&solution);
+        }
+        Format::Json => {
+            #[derive(Serialize)]
+            struct Output {
+                puzzle: String,
+                pieces: Vec<PlacedCubes>,
+            }
+            print_json(&Output {
+                puzzle: puzzle.name(),
+                pieces: solution,
+            });
+        }
+        _ => unreachable!("rejected by Options::accepting"),
+    }
+    Ok(())
+}
+
+// a puzzle book for every day of the year, written to a directory; what was written is listed on stdout
+fn book(options: &Options) -> Result<(), u32> {
+    let board = options.modified_board();
+    let year = match options.year {
+        Some(year) => year,
+        None => options.today()?.year().unwrap_or_default(),
+    };
+    let directory = options.out.clone().unwrap_or_else(|| format!("book-{}", year));
+
+    let days = PuzzleBook::new(&board, year)
+        .with_search(options.search())
+        .with_hide(options.hide)
+        .with_style(options.style(vec![]))
+        .write(std::path::Path::new(&directory))
+        .map_err(|e| {
+            eprintln!("{}", e);
+            OUTPUT_FAILED
+        })?;
+
+    let written = days.iter().filter(|day| day.status == "written").count();
+    match options.format {
+        Format::Text => {
+            for day in days.iter().filter(|day| day.status != "written") {
+                match day.status {
+                    "invalid" => outln!("{}: can't be shown on this board, left out", day.date),
+                    _ => outln!("{}: no solution, left out", day.date),
+                }
+            }
+            outln!("{} of {} dates written to {}/index.html", written, days.len(), directory);
+        }
+        Format::Json => {
+            #[derive(Serialize)]
+            struct Output {
+                board: String,
+                year: i32,
+                directory: String,
+                index: String,
+                days: Vec<book::BookDay>,
+            }
+            print_json(&Output {
+                board: board.name(),
+                year,
+                index: format!("{}/index.html", directory),
+                directory,
+                days,
+            });
+        }
+        _ => unreachable!("rejected by Options::accepting"),
+    }
+    if written == 0 {
+        return Err(solver::NO_SOLUTION_FOUND);
+    }
+    Ok(())
+}
+
+// the board and its pieces, to make on a laser cutter
+fn cut(options: &Options) -> Result<(), u32> {
+    let board = options.modified_board();
+    let mut laser = LaserCut::new();
+    if let Some(cell_size) = options.cell_size {
+        laser = laser.with_cell_size(cell_size);
+    }
+    if let Some(kerf) = options.kerf {
+        laser = laser.with_kerf(kerf);
+    }
+    check_inset("--kerf", laser.kerf(), laser.cell_size(), board.lattice())?;
+    match options.format {
+        Format::Svg => out!("{}", laser.svg(&board)),
+        Format::Dxf => out!("{}", laser.dxf(&board)),
+        _ => unreachable!("rejected by Options::accepting"),
+    }
+    Ok(())
+}
+
+// the board's pieces and a tray for them, to 3d print
+fn stl(options: &Options) -> Result<(), u32> {
+    let board = options.modified_board();
+    let mut model = StlModel::new();
+    if let Some(cell_size) = options.cell_size {
+        model = model.with_cell_size(cell_size);
+    }
+    if let Some(height) = options.height {
+        model = model.with_height(height);
+    }
+    if let Some(clearance) = options.clearance {
+        model = model.with_clearance(clearance);
+    }
+    check_inset("--clearance", model.clearance(), model.cell_size(), board.lattice())?;
+    let mesh = model.mesh(&board, options.part);
+    match options.format {
+        Format::Stl => out!("{}", mesh.ascii()),
+        Format::StlBinary => write_bytes(&mesh.binary(), "model.stl")?,
+        _ => unreachable!("rejected by Options::accepting"),
+    }
+    Ok(())
+}
+
+// draw the board itself, with every cell labelled; as text, a board file that loads back with --board-file
+fn board(options: &Options) -> Result<(), u32> {
+    let board = options.modified_board();
+    match options.format {
+        Format::Text => match FileBoard::write(&board) {
+            Ok(text) => out!("{}", text),
+            Err(reason) => {
+                eprintln!("{} (--format json writes every cell)", reason);
+                return Err(INVALID_ARGUMENTS);
+            }
+        },
+        Format::Json => {
+            #[derive(Serialize)]
+            struct Cell {
+                coordinate: Coordinate,
+                label: Option<String>,
+            }
+            #[derive(Serialize)]
+            struct Output {
+                board: String,
+                cells: Vec<Cell>,
+            }
+            let labels = board.labels();
+            print_json(&Output {
+                board: board.name(),
+                cells: board
+                    .coors()
+                    .into_iter()
+                    .map(|coor| Cell {
+                        coordinate: coor,
+                        label: labels.get(&coor).cloned(),
+                    })
+                    .collect(),
+            });
+        }
+        Format::Svg => out!("{}", SvgRenderer::new(options.style(vec![])).board(&board)),
+        Format::Tikz => out!("{}", TikzRenderer::new(options.hex_style(vec![])?).board(&board)),
+        #[cfg(feature = "png")]
+        Format::Png => write_png(png::PngRenderer::new(options.hex_style(vec![])?).board(&board))?,
+        _ => unreachable!("rejected by Options::accepting"),
+    }
+    Ok(())
+}
+
+// png is binary, so it only goes to a file or a pipe
+#[cfg(feature = "png")]
+fn write_png(image: Result<Vec<u8>, png::PngError>) -> Result<(), u32> {
+    let image = image.map_err(|e| {
+        eprintln!("{}", e);
+        OUTPUT_FAILED
+    })?;
+    write_bytes(&image, "solution.png")
+}
+
+// binary output, to a file or a pipe but not the terminal; the file name is for the hint
+fn write_bytes(bytes: &[u8], example: &str) -> Result<(), u32> {
+    use std::io::IsTerminal;
+
+    let mut stdout = std::io::stdout().lock();
+    if stdout.is_terminal() {
+        eprintln!("not writing binary output to the terminal; redirect it to a file (eg. > {})", example);
+        return Err(INVALID_ARGUMENTS);
+    }
+    written(stdout.write_all(bytes));
+    Ok(())
+}
+
+// output piped into something that stops reading early (eg. `| head`) isn't an error: there's just
+// nobody left to write for, so the program stops there (where println! would panic)
+fn written(result: std::io::Result<()>) {
+    match result {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => std::process::exit(0),
+        Err(e) => {
+            eprintln!("couldn't write the output: {}", e);
+            std::process::exit(OUTPUT_FAILED as i32);
+        }
+    }
+}
+
+fn find_polycube_puzzle(name: &str) -> Option<&'static dyn PolycubePuzzle> {
+    CUSTOM_POLYCUBES
         .iter()
-        .flat_map(|tile| tile.iter().map(|coor| coor.y))
-        .max()
-        .unwrap_or(0);
+        .find(|p| p.name().eq_ignore_ascii_case(name))
+        .copied()
+}
 
-    let mut output = vec![vec![' '; (max_x + 1) as usize]; (max_y + 1) as usize];
+fn find_board(name: &str) -> Option<&'static dyn CustomBoard> {
+    CUSTOM_BOARDS
+        .iter()
+        .chain(MESSAGE_BOARDS.iter())
+        .find(|b| b.name().eq_ignore_ascii_case(name))
+        .copied()
+}
+
+// print a 3d solution one layer at a time, from the bottom up
+fn print_layers(solution: &[PlacedCubes]) {
+    const PIECE_MARKERS: [char; 16] = [
+        'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'J', 'K', 'L', 'M', 'N', 'P', 'Q', 'R',
+    ];
 
-    let random_offset = rand::random::<u32>() as usize;
-    for (i, tile) in tiles.iter().enumerate().skip(hide_tiles) {
-        let marker = TILE_MARKERS[(i + random_offset) % TILE_MARKERS.len()];
-        for coor in tile {
-            output[coor.y as usize][coor.x as usize] = marker;
+    let cubes = || solution.iter().flat_map(|placed| placed.cubes.iter());
+    let width = cubes().map(|coor| coor.x + 1).max().unwrap_or(0) as usize;
+    let depth = cubes().map(|coor| coor.y + 1).max().unwrap_or(0) as usize;
+    let height = cubes().map(|coor| coor.z + 1).max().unwrap_or(0) as usize;
+
+    // empty spots (outside the container) are shown as '.', so each layer keeps its shape
+    let blank_row: Vec<char> = (0..width * 2).map(|i| if i % 2 == 0 { '.' } else { ' ' }).collect();
+    let mut layers = vec![vec![blank_row; depth]; height];
+    for (i, placed) in solution.iter().enumerate() {
+        for coor in placed.cubes.iter() {
+            layers[coor.z as usize][coor.y as usize][coor.x as usize * 2] = PIECE_MARKERS[i % PIECE_MARKERS.len()];
         }
     }
 
-    for row in output {
-        for cell in row {
-            print!("{} ", cell);
+    for (z, layer) in layers.iter().enumerate() {
+        outln!("layer {}:", z + 1);
+        for row in layer {
+            outln!("{}", row.iter().collect::<String>().trim_end());
         }
-        println!();
+    }
+    for (i, placed) in solution.iter().enumerate() {
+        outln!("{}: piece {}", PIECE_MARKERS[i % PIECE_MARKERS.len()], placed.piece);
     }
 }
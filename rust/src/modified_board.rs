@@ -0,0 +1,162 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::common::{AllTime, Coordinate, CustomBoard, Piece, PieceGroup, SolveMode, Target, Tile, TimeField, Wrap};
+use crate::lattice::Lattice;
+
+// an existing board with some of its pieces swapped out, taken away (eg. the ones that got lost),
+// or with extra pieces added
+pub struct ModifiedBoard<'a> {
+    board: &'a dyn CustomBoard,
+    missing: Vec<usize>, // indices into board.pieces(); each mention removes one copy
+    replaced: Vec<(usize, Tile)>, // index into board.pieces(), and the shape to use instead
+    extra: Vec<Tile>,
+}
+
+impl<'a> ModifiedBoard<'a> {
+    pub fn new(board: &'a dyn CustomBoard) -> ModifiedBoard<'a> {
+        ModifiedBoard {
+            board,
+            missing: vec![],
+            replaced: vec![],
+            extra: vec![],
+        }
+    }
+
+    pub fn without_piece(mut self, index: usize) -> ModifiedBoard<'a> {
+        self.missing.push(index);
+        self
+    }
+
+    // every copy of the piece takes on the new shape
+    pub fn replacing_piece(mut self, index: usize, tile: Tile) -> ModifiedBoard<'a> {
+        self.replaced.retain(|(i, _)| *i != index);
+        self.replaced.push((index, tile));
+        self
+    }
+
+    pub fn with_extra_piece(mut self, tile: Tile) -> ModifiedBoard<'a> {
+        self.extra.push(tile);
+        self
+    }
+
+    // remaining copies of each of the original pieces, in the original order
+    fn remaining_counts(&self) -> Vec<usize> {
+        let mut counts: Vec<usize> = self.board.pieces().iter().map(|p| p.count).collect();
+        for index in self.missing.iter() {
+            if let Some(count) = counts.get_mut(*index) {
+                *count = count.saturating_sub(1);
+            }
+        }
+        counts
+    }
+}
+
+impl CustomBoard for ModifiedBoard<'_> {
+    fn name(&self) -> String {
+        let mut changes: Vec<String> = Vec::new();
+        if !self.missing.is_empty() {
+            let missing: Vec<String> = self.missing.iter().map(|i| i.to_string()).collect();
+            changes.push(format!("missing pieces {}", missing.join(", ")));
+        }
+        if !self.replaced.is_empty() {
+            let replaced: Vec<String> = self.replaced.iter().map(|(i, _)| i.to_string()).collect();
+            changes.push(format!("replaced pieces {}", replaced.join(", ")));
+        }
+        if !self.extra.is_empty() {
+            changes.push(format!("{} extra pieces", self.extra.len()));
+        }
+
+        if changes.is_empty() {
+            return self.board.name();
+        }
+        format!("{} ({})", self.board.name(), changes.join("; "))
+    }
+
+    fn tiles(&self) -> Vec<Tile> {
+        self.pieces().into_iter().map(|p| p.tile).collect()
+    }
+
+    fn pieces(&self) -> Vec<Piece> {
+        let mut pieces: Vec<Piece> = self
+            .board
+            .pieces()
+            .into_iter()
+            .enumerate()
+            .map(|(i, piece)| match self.replaced.iter().find(|(index, _)| *index == i) {
+                // a new shape has none of the old shape's orientation rules
+                Some((_, tile)) => Piece::new(tile.clone())
+                    .with_count(piece.count)
+                    .with_usage(piece.usage),
+                None => piece,
+            })
+            .zip(self.remaining_counts())
+            .filter(|(_, count)| *count > 0)
+            .map(|(piece, count)| piece.with_count(count))
+            .collect();
+
+        for tile in self.extra.iter() {
+            pieces.push(Piece::new(tile.clone()));
+        }
+        pieces
+    }
+
+    fn piece_groups(&self) -> Vec<PieceGroup> {
+        // pieces that are entirely gone shift the indices of everything after them
+        let counts = self.remaining_counts();
+        let mut new_index: Vec<Option<usize>> = Vec::with_capacity(counts.len());
+        let mut next = 0;
+        for count in counts.iter() {
+            if *count > 0 {
+                new_index.push(Some(next));
+                next += 1;
+            } else {
+                new_index.push(None);
+            }
+        }
+
+        self.board
+            .piece_groups()
+            .into_iter()
+            .map(|group| PieceGroup {
+                pieces: group.pieces.iter().filter_map(|i| new_index.get(*i).copied().flatten()).collect(),
+                pick: group.pick,
+            })
+            .collect()
+    }
+
+    fn solve_mode(&self) -> SolveMode {
+        self.board.solve_mode()
+    }
+
+    fn coors(&self) -> Vec<Coordinate> {
+        self.board.coors()
+    }
+
+    fn lattice(&self) -> Lattice {
+        self.board.lattice()
+    }
+
+    fn wrap(&self) -> Wrap {
+        self.board.wrap()
+    }
+
+    fn point_in_time(&self, pit: &AllTime) -> Option<HashSet<Coordinate>> {
+        self.board.point_in_time(pit)
+    }
+
+    fn labels(&self) -> HashMap<Coordinate, String> {
+        self.board.labels()
+    }
+
+    fn point_in_time_alternatives(&self, pit: &AllTime) -> Vec<HashSet<Coordinate>> {
+        self.board.point_in_time_alternatives(pit)
+    }
+
+    fn time_fields(&self) -> Vec<TimeField> {
+        self.board.time_fields()
+    }
+
+    fn target_alternatives(&self, target: &Target) -> Vec<HashSet<Coordinate>> {
+        self.board.target_alternatives(target)
+    }
+}
@@ -0,0 +1,190 @@
+use ab_glyph::{Font, FontRef, PxScale, ScaleFont, point};
+use tiny_skia::{Color, FillRule, LineCap, Paint, PathBuilder, Pixmap, PremultipliedColorU8, Stroke, Transform};
+
+use crate::common::{CustomBoard, Solution};
+use crate::drawing::{Drawing, Point, Style, center, label_size};
+
+// draws a board as a PNG image, the same way SvgRenderer does. Labels use the Noto Sans font built
+// into the binary, so nothing has to be installed
+pub struct PngRenderer {
+    cell_size: f64,     // in pixels, between neighbouring cells
+    colors: Vec<Color>, // the style's colours, by piece index
+    style: Style,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PngError {
+    Empty,          // the board has no cells, so there's nothing to draw
+    Encode(String), // the image couldn't be turned into a PNG
+}
+
+impl std::fmt::Display for PngError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PngError::Empty => write!(f, "the board has no cells to draw"),
+            PngError::Encode(reason) => write!(f, "couldn't encode the png: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for PngError {}
+
+impl PngRenderer {
+    pub fn new(style: Style) -> PngRenderer {
+        PngRenderer {
+            cell_size: 64.0,
+            colors: style.rgb_colors().into_iter().map(|(r, g, b)| Color::from_rgba8(r, g, b, 0xFF)).collect(),
+            style,
+        }
+    }
+
+    pub fn board(&self, board: &dyn CustomBoard) -> Result<Vec<u8>, PngError> {
+        self.render(&Drawing::new(board, &[], &[], self.cell_size, self.margin()))
+    }
+
+    pub fn solution(&self, board: &dyn CustomBoard, solution: &Solution) -> Result<Vec<u8>, PngError> {
+        self.render(&Drawing::solution(board, solution, &self.style.hidden, self.cell_size, self.margin()))
+    }
+
+    fn margin(&self) -> f64 {
+        self.cell_size / 4.0
+    }
+
+    fn render(&self, drawing: &Drawing) -> Result<Vec<u8>, PngError> {
+        if drawing.cells.is_empty() {
+            return Err(PngError::Empty);
+        }
+        let mut pixmap =
+            Pixmap::new(drawing.width.ceil() as u32, drawing.height.ceil() as u32).ok_or(PngError::Empty)?;
+        pixmap.fill(Color::WHITE);
+
+        let white = Color::WHITE;
+        let grid = Color::from_rgba8(0xBB, 0xBB, 0xBB, 0xFF);
+        let ink = Color::from_rgba8(0x22, 0x22, 0x22, 0xFF);
+        let (r, g, b) = self.style.target_rgb();
+        let target = Color::from_rgba8(r, g, b, 0xFF);
+
+        for cell in drawing.cells.iter() {
+            fill(&mut pixmap, &cell.polygon, if cell.target { target } else { white });
+            stroke(&mut pixmap, &closed(&cell.polygon), grid, 1.0);
+        }
+
+        if self.style.labels {
+            let font = FontRef::try_from_slice(notosans::REGULAR_TTF).expect("the built-in font is valid");
+            let label_color = Color::from_rgba8(0x44, 0x44, 0x44, 0xFF);
+            for cell in drawing.cells.iter() {
+                if let Some(label) = &cell.label {
+                    let size = self.cell_size * label_size(label);
+                    draw_text(&mut pixmap, &font, label, center(&cell.polygon), size as f32, label_color);
+                }
+            }
+        }
+
+        // pieces are stroked in their own colour too, so no seams show between their cells
+        for piece in drawing.pieces.iter() {
+            let color = self.colors[piece.piece % self.colors.len()];
+            for cell in piece.cells.iter() {
+                fill(&mut pixmap, cell, color);
+                stroke(&mut pixmap, &closed(cell), color, 1.0);
+            }
+            stroke(&mut pixmap, &piece.outline, ink, (self.cell_size / 16.0) as f32);
+        }
+
+        stroke(&mut pixmap, &drawing.border, ink, (self.cell_size / 12.0) as f32);
+        pixmap.encode_png().map_err(|e| PngError::Encode(e.to_string()))
+    }
+}
+
+fn paint(color: Color) -> Paint<'static> {
+    let mut paint = Paint::default();
+    paint.set_color(color);
+    paint.anti_alias = true;
+    paint
+}
+
+fn fill(pixmap: &mut Pixmap, polygon: &[Point], color: Color) {
+    let mut builder = PathBuilder::new();
+    for (i, (x, y)) in polygon.iter().enumerate() {
+        if i == 0 {
+            builder.move_to(*x as f32, *y as f32);
+        } else {
+            builder.line_to(*x as f32, *y as f32);
+        }
+    }
+    builder.close();
+    if let Some(path) = builder.finish() {
+        pixmap.fill_path(&path, &paint(color), FillRule::Winding, Transform::identity(), None);
+    }
+}
+
+// every edge of the polygon, including the one back to the start
+fn closed(polygon: &[Point]) -> Vec<(Point, Point)> {
+    (0..polygon.len())
+        .map(|i| (polygon[i], polygon[(i + 1) % polygon.len()]))
+        .collect()
+}
+
+fn stroke(pixmap: &mut Pixmap, edges: &[(Point, Point)], color: Color, width: f32) {
+    let mut builder = PathBuilder::new();
+    for (from, to) in edges {
+        builder.move_to(from.0 as f32, from.1 as f32);
+        builder.line_to(to.0 as f32, to.1 as f32);
+    }
+    let stroke = Stroke {
+        width,
+        line_cap: LineCap::Round,
+        ..Stroke::default()
+    };
+    if let Some(path) = builder.finish() {
+        pixmap.stroke_path(&path, &paint(color), &stroke, Transform::identity(), None);
+    }
+}
+
+// centred on the point, both ways
+fn draw_text(pixmap: &mut Pixmap, font: &FontRef, text: &str, (x, y): Point, size: f32, color: Color) {
+    let scale = PxScale::from(size);
+    let scaled = font.as_scaled(scale);
+    let ids: Vec<_> = text.chars().map(|ch| font.glyph_id(ch)).collect();
+    let mut width = 0.0;
+    for (i, id) in ids.iter().enumerate() {
+        if i > 0 {
+            width += scaled.kern(ids[i - 1], *id);
+        }
+        width += scaled.h_advance(*id);
+    }
+
+    let baseline = y as f32 + (scaled.ascent() + scaled.descent()) / 2.0;
+    let mut caret = x as f32 - width / 2.0;
+    let (pixel_width, pixel_height) = (pixmap.width() as i32, pixmap.height() as i32);
+    let color = color.to_color_u8();
+    for (i, id) in ids.iter().enumerate() {
+        if i > 0 {
+            caret += scaled.kern(ids[i - 1], *id);
+        }
+        let glyph = id.with_scale_and_position(scale, point(caret, baseline));
+        caret += scaled.h_advance(*id);
+        let Some(outlined) = font.outline_glyph(glyph) else {
+            continue; // eg. a space
+        };
+
+        // the image is opaque everywhere, so blending only has to mix the colours
+        let bounds = outlined.px_bounds();
+        let pixels = pixmap.pixels_mut();
+        outlined.draw(|gx, gy, coverage| {
+            let (px, py) = (bounds.min.x as i32 + gx as i32, bounds.min.y as i32 + gy as i32);
+            if px < 0 || py < 0 || px >= pixel_width || py >= pixel_height {
+                return;
+            }
+            let pixel = &mut pixels[(py * pixel_width + px) as usize];
+            let mix = |under: u8, over: u8| (under as f32 * (1.0 - coverage) + over as f32 * coverage).round() as u8;
+            if let Some(mixed) = PremultipliedColorU8::from_rgba(
+                mix(pixel.red(), color.red()),
+                mix(pixel.green(), color.green()),
+                mix(pixel.blue(), color.blue()),
+                0xFF,
+            ) {
+                *pixel = mixed;
+            }
+        });
+    }
+}
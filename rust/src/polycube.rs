@@ -0,0 +1,202 @@
+use serde::{Deserialize, Serialize};
+
+use crate::common::Symmetry;
+
+// a cube in a 3d puzzle; z is the layer, counting up from the bottom
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Coordinate3 {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+pub fn c3(x: i32, y: i32, z: i32) -> Coordinate3 {
+    Coordinate3 { x, y, z }
+}
+
+pub type Cubes = Vec<Coordinate3>;
+
+// a piece of a 3d puzzle; unlike flat pieces these can't be mirrored by picking them up and
+// turning them over, so by default they only take the 24 rotations
+#[derive(Debug, Clone)]
+pub struct PolycubePiece {
+    pub cubes: Cubes,
+    pub symmetry: Symmetry, // All adds the mirror images, for 48 orientations
+    pub count: usize,
+}
+
+impl PolycubePiece {
+    pub fn new(cubes: Cubes) -> PolycubePiece {
+        PolycubePiece {
+            cubes,
+            symmetry: Symmetry::Rotations,
+            count: 1,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn with_symmetry(mut self, symmetry: Symmetry) -> PolycubePiece {
+        self.symmetry = symmetry;
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn with_count(mut self, count: usize) -> PolycubePiece {
+        self.count = count;
+        self
+    }
+}
+
+// a 3d packing puzzle: fill every cell of the container with the pieces
+pub trait PolycubePuzzle {
+    #[allow(dead_code)]
+    fn name(&self) -> String;
+    fn pieces(&self) -> Vec<PolycubePiece>;
+    fn cells(&self) -> Vec<Coordinate3>;
+}
+
+// one piece of a solution, as it sits in the container
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlacedCubes {
+    pub piece: usize,       // index into PolycubePuzzle::pieces()
+    pub copy: usize,        // which copy of the piece, when there are several
+    pub orientation: usize, // index into Polycube::orientations() for the piece
+    pub anchor: Coordinate3, // where cubes[0] of the piece ended up
+    pub cubes: Cubes,       // every cell filled by the piece
+}
+
+pub struct Polycube {}
+
+impl Polycube {
+    // a quarter turn around the x axis, about the origin
+    fn turn_x(cubes: &mut Cubes) {
+        for coor in cubes.iter_mut() {
+            (coor.y, coor.z) = (-coor.z, coor.y);
+        }
+    }
+
+    // a quarter turn around the y axis, about the origin
+    fn turn_y(cubes: &mut Cubes) {
+        for coor in cubes.iter_mut() {
+            (coor.x, coor.z) = (coor.z, -coor.x);
+        }
+    }
+
+    // a quarter turn around the z axis, about the origin
+    fn turn_z(cubes: &mut Cubes) {
+        for coor in cubes.iter_mut() {
+            (coor.x, coor.y) = (-coor.y, coor.x);
+        }
+    }
+
+    fn mirror(cubes: &mut Cubes) {
+        for coor in cubes.iter_mut() {
+            coor.x = -coor.x;
+        }
+    }
+
+    // all 24 rotations of the cubes: point the top at each of the six faces, then spin four times
+    fn rotations(cubes: &Cubes) -> Vec<Cubes> {
+        let mut out: Vec<Cubes> = Vec::with_capacity(24);
+        for face in 0..6 {
+            let mut current = cubes.clone();
+            match face {
+                0..4 => (0..face).for_each(|_| Self::turn_x(&mut current)),
+                4 => Self::turn_y(&mut current),
+                _ => (0..3).for_each(|_| Self::turn_y(&mut current)),
+            }
+            for _ in 0..4 {
+                out.push(current.clone());
+                Self::turn_z(&mut current);
+            }
+        }
+        out
+    }
+
+    // every distinct way the piece can sit, with the cubes kept in their original order
+    // (so cubes[0] is always the same cube of the piece)
+    pub fn orientations(cubes: &Cubes, symmetry: Symmetry) -> Vec<Cubes> {
+        let candidates = match symmetry {
+            Symmetry::Fixed => vec![cubes.clone()],
+            Symmetry::Rotations => Self::rotations(cubes),
+            Symmetry::All => {
+                let mut mirrored = cubes.clone();
+                Self::mirror(&mut mirrored);
+                let mut out = Self::rotations(cubes);
+                out.extend(Self::rotations(&mirrored));
+                out
+            }
+        };
+
+        let mut orientations: Vec<Cubes> = Vec::with_capacity(candidates.len());
+        for candidate in candidates {
+            let shape = Self::normalized(&candidate);
+            if !orientations.iter().any(|o| Self::normalized(o) == shape) {
+                orientations.push(candidate);
+            }
+        }
+        orientations
+    }
+
+    // sorted coordinates, moved so the smallest x, y and z are all 0
+    pub fn normalized(cubes: &Cubes) -> Cubes {
+        let min_x = cubes.iter().map(|coor| coor.x).min().unwrap_or(0);
+        let min_y = cubes.iter().map(|coor| coor.y).min().unwrap_or(0);
+        let min_z = cubes.iter().map(|coor| coor.z).min().unwrap_or(0);
+        let mut out: Cubes = cubes
+            .iter()
+            .map(|coor| c3(coor.x - min_x, coor.y - min_y, coor.z - min_z))
+            .collect();
+        out.sort_by_key(|coor| (coor.z, coor.y, coor.x));
+        out
+    }
+
+    pub fn translate(cubes: &mut [Coordinate3], offset: &Coordinate3) {
+        for coor in cubes.iter_mut() {
+            coor.x += offset.x;
+            coor.y += offset.y;
+            coor.z += offset.z;
+        }
+    }
+
+    pub fn neighbors(coor: &Coordinate3) -> [Coordinate3; 6] {
+        let (x, y, z) = (coor.x, coor.y, coor.z);
+        [
+            c3(x + 1, y, z),
+            c3(x - 1, y, z),
+            c3(x, y + 1, z),
+            c3(x, y - 1, z),
+            c3(x, y, z + 1),
+            c3(x, y, z - 1),
+        ]
+    }
+
+    // a solid width x depth x height box
+    pub fn cuboid(width: i32, depth: i32, height: i32) -> Cubes {
+        let mut out: Cubes = Vec::new();
+        for z in 0..height {
+            for y in 0..depth {
+                for x in 0..width {
+                    out.push(c3(x, y, z));
+                }
+            }
+        }
+        out
+    }
+
+    // a stepped pyramid on a square base: each layer is one cube smaller on every side
+    // than the layer below it (eg. 5x5, 3x3, 1x1)
+    pub fn pyramid(base: i32) -> Cubes {
+        let mut out: Cubes = Vec::new();
+        let mut z = 0;
+        while base - 2 * z > 0 {
+            for y in z..(base - z) {
+                for x in z..(base - z) {
+                    out.push(c3(x, y, z));
+                }
+            }
+            z += 1;
+        }
+        out
+    }
+}
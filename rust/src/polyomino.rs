@@ -0,0 +1,174 @@
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+use crate::common::{Coordinate, Symmetry, Tile, c};
+use crate::lattice::Lattice;
+use crate::tile_helper::TileHelper;
+
+// the polyominoes with well-known names, up to pentominoes
+fn named_polyominoes() -> Vec<(&'static str, Tile)> {
+    vec![
+        ("monomino", vec![c(0, 0)]),
+        ("domino", vec![c(0, 0), c(1, 0)]),
+        ("I-tromino", vec![c(0, 0), c(1, 0), c(2, 0)]),
+        ("L-tromino", vec![c(0, 0), c(1, 0), c(0, 1)]),
+        ("I-tetromino", vec![c(0, 0), c(1, 0), c(2, 0), c(3, 0)]),
+        ("O-tetromino", vec![c(0, 0), c(1, 0), c(0, 1), c(1, 1)]),
+        ("T-tetromino", vec![c(0, 0), c(1, 0), c(2, 0), c(1, 1)]),
+        ("S-tetromino", vec![c(1, 0), c(2, 0), c(0, 1), c(1, 1)]),
+        ("L-tetromino", vec![c(0, 0), c(0, 1), c(0, 2), c(1, 2)]),
+        ("F-pentomino", vec![c(1, 0), c(2, 0), c(0, 1), c(1, 1), c(1, 2)]),
+        ("I-pentomino", vec![c(0, 0), c(1, 0), c(2, 0), c(3, 0), c(4, 0)]),
+        ("L-pentomino", vec![c(0, 0), c(0, 1), c(0, 2), c(0, 3), c(1, 3)]),
+        ("N-pentomino", vec![c(1, 0), c(1, 1), c(0, 2), c(1, 2), c(0, 3)]),
+        ("P-pentomino", vec![c(0, 0), c(1, 0), c(0, 1), c(1, 1), c(0, 2)]),
+        ("T-pentomino", vec![c(0, 0), c(1, 0), c(2, 0), c(1, 1), c(1, 2)]),
+        ("U-pentomino", vec![c(0, 0), c(2, 0), c(0, 1), c(1, 1), c(2, 1)]),
+        ("V-pentomino", vec![c(0, 0), c(0, 1), c(0, 2), c(1, 2), c(2, 2)]),
+        ("W-pentomino", vec![c(0, 0), c(0, 1), c(1, 1), c(1, 2), c(2, 2)]),
+        ("X-pentomino", vec![c(1, 0), c(0, 1), c(1, 1), c(2, 1), c(1, 2)]),
+        ("Y-pentomino", vec![c(1, 0), c(0, 1), c(1, 1), c(1, 2), c(1, 3)]),
+        ("Z-pentomino", vec![c(0, 0), c(1, 0), c(1, 1), c(1, 2), c(2, 2)]),
+    ]
+}
+
+// (name, canonical form) of every polyomino from monomino through hexomino
+fn catalogue() -> &'static Vec<(String, Tile)> {
+    static CATALOGUE: OnceLock<Vec<(String, Tile)>> = OnceLock::new();
+    CATALOGUE.get_or_init(|| {
+        let mut out: Vec<(String, Tile)> = named_polyominoes()
+            .into_iter()
+            .map(|(name, tile)| (name.to_string(), Polyomino::canonical(&tile)))
+            .collect();
+
+        // hexominoes have no standard letter names, so they are numbered (1 - 35) in canonical order
+        let pentominoes: Vec<Tile> = out
+            .iter()
+            .filter(|(_, tile)| tile.len() == 5)
+            .map(|(_, tile)| tile.clone())
+            .collect();
+        for (i, hexomino) in Polyomino::grow(&pentominoes).into_iter().enumerate() {
+            out.push((format!("hexomino-{:02}", i + 1), hexomino));
+        }
+        out
+    })
+}
+
+pub struct Polyomino {}
+
+impl Polyomino {
+    // the same shape in every orientation (including flipped) has the same canonical form
+    pub fn canonical(tile: &Tile) -> Tile {
+        TileHelper::orientations(tile, Symmetry::All, Lattice::Square)
+            .iter()
+            .map(TileHelper::normalized)
+            .min_by_key(Self::sort_key)
+            .unwrap_or_default()
+    }
+
+    // canonical name of the tile's shape (eg. "Z-pentomino"), whatever its orientation or position;
+    // None for anything bigger than a hexomino, or that isn't a single connected shape
+    pub fn name(tile: &Tile) -> Option<String> {
+        if tile.is_empty() || tile.len() > 6 {
+            return None;
+        }
+        let canonical = Self::canonical(tile);
+        catalogue()
+            .iter()
+            .find(|(_, known)| *known == canonical)
+            .map(|(name, _)| name.clone())
+    }
+
+    // the canonical form of a named polyomino, eg. "P-pentomino" or "hexomino-07"
+    pub fn from_name(name: &str) -> Option<Tile> {
+        catalogue()
+            .iter()
+            .find(|(known, _)| known.eq_ignore_ascii_case(name))
+            .map(|(_, tile)| tile.clone())
+    }
+
+    // a polyomino name (on a square lattice), or anything TileHelper::parse understands
+    pub fn parse_piece(value: &str, lattice: Lattice) -> Option<Tile> {
+        let named = match lattice {
+            Lattice::Square => Self::from_name(value),
+            Lattice::Hexagonal | Lattice::Triangular => None,
+        };
+        named.or_else(|| TileHelper::parse(value, lattice))
+    }
+
+    // every distinct (canonical) polyomino that is one cell bigger than one of the given ones
+    fn grow(tiles: &[Tile]) -> Vec<Tile> {
+        let mut seen: HashSet<Tile> = HashSet::new();
+        for tile in tiles {
+            for coor in tile.iter() {
+                for (dx, dy) in [(1, 0), (-1, 0), (0, 1), (0, -1)] {
+                    let next = Coordinate {
+                        x: coor.x + dx,
+                        y: coor.y + dy,
+                    };
+                    if tile.contains(&next) {
+                        continue;
+                    }
+                    let mut bigger = tile.clone();
+                    bigger.push(next);
+                    seen.insert(Self::canonical(&bigger));
+                }
+            }
+        }
+
+        let mut out: Vec<Tile> = seen.into_iter().collect();
+        out.sort_by_key(Self::sort_key);
+        out
+    }
+
+    fn sort_key(tile: &Tile) -> Vec<(i32, i32)> {
+        tile.iter().map(|coor| (coor.y, coor.x)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // every way the tile can be turned over and round, moved away from the origin
+    fn orientations(tile: &Tile) -> Vec<Tile> {
+        let lattice = Lattice::Square;
+        let mut out: Vec<Tile> = Vec::new();
+        let mut turned = tile.clone();
+        for _ in 0..2 {
+            for _ in 0..lattice.rotation_count() {
+                lattice.rotate(&mut turned);
+                out.push(turned.iter().map(|coor| c(coor.x + 3, coor.y - 7)).collect());
+            }
+            lattice.flip(&mut turned);
+        }
+        out
+    }
+
+    #[test]
+    fn named_shapes_are_recognised_in_every_orientation() {
+        for (name, tile) in named_polyominoes() {
+            for turned in orientations(&tile) {
+                assert_eq!(Polyomino::name(&turned).as_deref(), Some(name), "{:?}", turned);
+            }
+        }
+        let hexomino = Polyomino::from_name("hexomino-07").unwrap();
+        for turned in orientations(&hexomino) {
+            assert_eq!(Polyomino::name(&turned).as_deref(), Some("hexomino-07"), "{:?}", turned);
+        }
+    }
+
+    #[test]
+    fn other_shapes_have_no_name() {
+        // in two pieces, and too big
+        assert_eq!(Polyomino::name(&vec![c(0, 0), c(2, 0)]), None);
+        assert_eq!(Polyomino::name(&(0..7).map(|x| c(x, 0)).collect()), None);
+        assert_eq!(Polyomino::name(&vec![]), None);
+    }
+
+    #[test]
+    fn there_are_35_hexominoes() {
+        assert!(Polyomino::from_name("hexomino-35").is_some());
+        assert!(Polyomino::from_name("hexomino-36").is_none());
+    }
+}
@@ -0,0 +1,277 @@
+use serde::{Deserialize, Serialize};
+
+use crate::common::{Coordinate, CustomBoard, Orientation, PlacedPiece, Solution, Target};
+
+// the machine-readable form of a solution, the same from the command line (json, ndjson and csv) and
+// from wasm (JsTile is a PieceRecord). The version goes up whenever a field is renamed, removed or
+// changes meaning; fields can be added without changing it, so readers should ignore ones they don't know
+//
+// version 1:
+//   schema         always 1
+//   board          the board's name
+//   date           "YYYY-MM-DD" when solving for a date, otherwise null
+//   seed           the --seed the solution was found with, or null
+//   index          which solution this is when there are several (see the solutions command), from 0
+//   target         the cells left uncovered to show the date (or labels), in reading order
+//   target_labels  the labels of those cells, in the same order ("" for a cell without one)
+//   encoding       which of the board's ways of showing the target was used, from 0
+//   gaps           any other cells left uncovered
+//   hidden         ids of the pieces left out of a printed puzzle (see --hide)
+//   pieces         one PieceRecord per piece, ordered by piece and copy:
+//     id           position in pieces; the same from one solution to the next
+//     piece        index of the piece on the board
+//     copy         which copy of the piece, when the board has several, from 0
+//     name         polyomino name (eg. "Z-pentomino") on square boards, for up to hexominoes; otherwise null
+//     rotation     turns after flipping: quarter turns on square boards, sixth turns on the others
+//     flipped      whether the piece was flipped (before rotating)
+//     anchor       where the first cell of the piece, as the board defines it, ended up
+//     coordinates  every cell the piece covers
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SolutionRecord {
+    pub schema: u32,
+    pub board: String,
+    pub date: Option<String>,
+    pub seed: Option<u64>,
+    pub index: usize,
+    pub target: Vec<Coordinate>,
+    pub target_labels: Vec<String>,
+    pub encoding: usize,
+    pub gaps: Vec<Coordinate>,
+    pub hidden: Vec<usize>,
+    pub pieces: Vec<PieceRecord>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PieceRecord {
+    #[serde(default)]
+    pub id: usize,
+    pub piece: usize,
+    pub copy: usize,
+    pub name: Option<String>,
+    pub rotation: u32,
+    pub flipped: bool,
+    pub anchor: Coordinate,
+    pub coordinates: Vec<Coordinate>,
+}
+
+impl SolutionRecord {
+    pub fn new(board: &dyn CustomBoard, target: &Target, solution: &Solution) -> SolutionRecord {
+        let labels = board.labels();
+        SolutionRecord {
+            schema: SCHEMA_VERSION,
+            board: board.name(),
+            date: match target {
+                Target::Date(date) => date.iso_date(),
+                _ => None,
+            },
+            seed: None,
+            index: 0,
+            target: solution.target.clone(),
+            target_labels: solution
+                .target
+                .iter()
+                .map(|coor| labels.get(coor).cloned().unwrap_or_default())
+                .collect(),
+            encoding: solution.encoding,
+            gaps: solution.gaps.clone(),
+            hidden: vec![],
+            pieces: solution
+                .pieces
+                .iter()
+                .enumerate()
+                .map(|(id, placed)| PieceRecord::new(id, placed))
+                .collect(),
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn with_seed(mut self, seed: Option<u64>) -> SolutionRecord {
+        self.seed = seed;
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn with_index(mut self, index: usize) -> SolutionRecord {
+        self.index = index;
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn with_hidden(mut self, hidden: Vec<usize>) -> SolutionRecord {
+        self.hidden = hidden;
+        self
+    }
+
+    // one row per piece, under csv_header()
+    #[allow(dead_code)]
+    pub fn csv_rows(&self) -> Vec<String> {
+        self.pieces
+            .iter()
+            .map(|piece| {
+                let cells: Vec<String> = piece.coordinates.iter().map(|c| format!("{} {}", c.x, c.y)).collect();
+                let fields: Vec<String> = vec![
+                    self.schema.to_string(),
+                    self.board.clone(),
+                    self.date.clone().unwrap_or_default(),
+                    self.seed.map(|seed| seed.to_string()).unwrap_or_default(),
+                    self.index.to_string(),
+                    piece.id.to_string(),
+                    piece.piece.to_string(),
+                    piece.copy.to_string(),
+                    piece.name.clone().unwrap_or_default(),
+                    piece.rotation.to_string(),
+                    piece.flipped.to_string(),
+                    piece.anchor.x.to_string(),
+                    piece.anchor.y.to_string(),
+                    cells.join(";"),
+                    self.hidden.contains(&piece.id).to_string(),
+                ];
+                let fields: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+                fields.join(",")
+            })
+            .collect()
+    }
+}
+
+// the csv columns: the solution's fields, then the piece's (cells are "x y" pairs split by ';')
+#[allow(dead_code)]
+pub fn csv_header() -> &'static str {
+    "schema,board,date,seed,index,id,piece,copy,name,rotation,flipped,anchor_x,anchor_y,cells,hidden"
+}
+
+impl PieceRecord {
+    pub fn new(id: usize, placed: &PlacedPiece) -> PieceRecord {
+        PieceRecord {
+            id,
+            piece: placed.piece,
+            copy: placed.copy,
+            name: placed.name.clone(),
+            rotation: placed.orientation.rotation,
+            flipped: placed.orientation.flipped,
+            anchor: placed.anchor,
+            coordinates: placed.tile.clone(),
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn placed(&self) -> PlacedPiece {
+        PlacedPiece {
+            piece: self.piece,
+            copy: self.copy,
+            name: self.name.clone(),
+            orientation: Orientation {
+                rotation: self.rotation,
+                flipped: self.flipped,
+            },
+            anchor: self.anchor,
+            tile: self.coordinates.clone(),
+        }
+    }
+}
+
+// quoted when it has to be, with quotes doubled
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::c;
+
+    fn record(board: &str) -> SolutionRecord {
+        SolutionRecord {
+            schema: SCHEMA_VERSION,
+            board: board.to_string(),
+            date: Some("2026-03-05".to_string()),
+            seed: Some(7),
+            index: 2,
+            target: vec![c(2, 0), c(4, 1)],
+            target_labels: vec!["Mar".to_string(), "5".to_string()],
+            encoding: 0,
+            gaps: vec![],
+            hidden: vec![1],
+            pieces: vec![
+                PieceRecord {
+                    id: 0,
+                    piece: 0,
+                    copy: 0,
+                    name: Some("domino".to_string()),
+                    rotation: 1,
+                    flipped: false,
+                    anchor: c(0, 0),
+                    coordinates: vec![c(0, 0), c(0, 1)],
+                },
+                PieceRecord {
+                    id: 1,
+                    piece: 1,
+                    copy: 0,
+                    name: None,
+                    rotation: 0,
+                    flipped: true,
+                    anchor: c(1, 0),
+                    coordinates: vec![c(1, 0)],
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn csv_fields_are_quoted_when_they_have_to_be() {
+        assert_eq!(csv_field("Nova Scotia"), "Nova Scotia");
+        assert_eq!(csv_field("Month, Day"), "\"Month, Day\"");
+        assert_eq!(csv_field("Generic 'Tetromino' \"Board\""), "\"Generic 'Tetromino' \"\"Board\"\"\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+        assert_eq!(csv_field(""), "");
+    }
+
+    #[test]
+    fn csv_rows_line_up_with_the_header() {
+        let rows = record("Generic (Month, Day-of-Month) \"Board\"").csv_rows();
+        assert_eq!(
+            rows,
+            vec![
+                "1,\"Generic (Month, Day-of-Month) \"\"Board\"\"\",2026-03-05,7,2,0,0,0,domino,1,false,0,0,0 0;0 1,false",
+                "1,\"Generic (Month, Day-of-Month) \"\"Board\"\"\",2026-03-05,7,2,1,1,0,,0,true,1,0,1 0,true",
+            ]
+        );
+        assert_eq!(csv_header().split(',').count(), 15);
+        assert!(csv_header().starts_with("schema,"));
+    }
+
+    // version 1's fields, by name; renaming or removing one means a new version
+    #[test]
+    fn records_are_schema_version_1() {
+        assert_eq!(SCHEMA_VERSION, 1);
+        let json: serde_json::Value = serde_json::to_value(record("Nova Scotia Calendar Board")).unwrap();
+        assert_eq!(json["schema"], 1);
+        let mut fields: Vec<&String> = json.as_object().unwrap().keys().collect();
+        fields.sort();
+        assert_eq!(
+            fields,
+            vec![
+                "board", "date", "encoding", "gaps", "hidden", "index", "pieces", "schema", "seed", "target",
+                "target_labels"
+            ]
+        );
+        let mut piece_fields: Vec<&String> = json["pieces"][0].as_object().unwrap().keys().collect();
+        piece_fields.sort();
+        assert_eq!(
+            piece_fields,
+            vec!["anchor", "coordinates", "copy", "flipped", "id", "name", "piece", "rotation"]
+        );
+
+        // and they read back, ignoring fields added later
+        let mut newer = json.clone();
+        newer["added_later"] = serde_json::Value::Bool(true);
+        let read: SolutionRecord = serde_json::from_value(newer).unwrap();
+        assert_eq!(read.schema, 1);
+        assert_eq!(read.pieces[1].coordinates, vec![c(1, 0)]);
+    }
+}
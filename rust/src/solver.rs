@@ -1,61 +1,569 @@
+use std::collections::{HashMap, HashSet};
+
+use rand::{SeedableRng, rngs::StdRng, seq::SliceRandom};
+
 use crate::{
-    active_board::ActiveBoard,
-    common::{AllTime, CustomBoard, Tile},
-    tile_helper::TileHelper,
+    active_board::{ActiveBoard, Packing, Placement},
+    active_box::ActiveBox,
+    common::{Coordinate, CustomBoard, PlacedPiece, SolveMode, Solution, Target, Usage},
+    exact_cover::ExactCover,
+    polycube::{PlacedCubes, PolycubePuzzle},
 };
 
-const NO_SOLUTION_FOUND: u32 = 3;
+pub const NO_SOLUTION_FOUND: u32 = 3;
+pub const AREA_MISMATCH: u32 = 4;
+pub const INVALID_TARGET: u32 = 5;
+pub const UNSUPPORTED_BY_BACKEND: u32 = 7;
 
-pub fn run(now: &AllTime, board: &dyn CustomBoard) -> Result<Vec<Tile>, u32> {
-    let mut active_board = ActiveBoard::from_custom(board, now);
+// how the pieces are searched for
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    Backtracking, // place the pieces one at a time, always filling the first open cell
+    ExactCover,   // Algorithm X over every placement at once; needs every piece used and every cell covered
+}
 
-    let mut solution: Vec<Tile> = Vec::new();
-    let found_solution = solve(&mut active_board, &mut solution);
-    if !found_solution {
-        return Err(NO_SOLUTION_FOUND);
+impl Backend {
+    #[allow(dead_code)]
+    pub fn from_name(name: &str) -> Option<Backend> {
+        match name.to_lowercase().as_str() {
+            "backtracking" => Some(Backend::Backtracking),
+            "exact-cover" | "dlx" => Some(Backend::ExactCover),
+            _ => None,
+        }
     }
+}
 
-    Ok(solution)
+// a search with its backend and random seed chosen; without a seed, every search can come up with
+// a different solution
+pub struct Search {
+    backend: Backend,
+    seed: Option<u64>,
 }
 
-// try to place a tile, then recurse to place the next tile
-pub fn solve(active_board: &mut ActiveBoard, solution: &mut Vec<Tile>) -> bool {
+impl Search {
+    pub fn new() -> Search {
+        Search {
+            backend: Backend::Backtracking,
+            seed: None,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn with_backend(mut self, backend: Backend) -> Search {
+        self.backend = backend;
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn with_seed(mut self, seed: Option<u64>) -> Search {
+        self.seed = seed;
+        self
+    }
+
+    pub fn rng(&self) -> StdRng {
+        match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_rng(&mut rand::rng()),
+        }
+    }
+
+    // cover everything on the board except the target, trying each way the board can show it in turn
+    pub fn run(&self, target: &Target, board: &dyn CustomBoard) -> Result<Solution, u32> {
+        let alternatives = board.target_alternatives(target);
+        if alternatives.is_empty() {
+            return Err(INVALID_TARGET);
+        }
+
+        let mut rng = self.rng();
+        for (encoding, avoid_points) in alternatives.iter().enumerate() {
+            let mut active_board = ActiveBoard::from_custom(board, avoid_points, &mut rng);
+            if !check_area(board, active_board.open_coor_count()) {
+                return Err(AREA_MISMATCH);
+            }
+
+            if let Some(mut pieces) = self.solve(&mut active_board, &mut rng)? {
+                pieces.sort_by_key(|placed| (placed.piece, placed.copy));
+                return Ok(Solution {
+                    pieces,
+                    target: sorted(avoid_points),
+                    encoding,
+                    gaps: vec![],
+                });
+            }
+        }
+
+        Err(NO_SOLUTION_FOUND)
+    }
+
+    // like run, but when the pieces have less area than the open cells (eg. one went missing),
+    // place every piece anyway and return the cells that had to be left uncovered as gaps
+    pub fn run_leaving_gaps(&self, target: &Target, board: &dyn CustomBoard) -> Result<Solution, u32> {
+        let alternatives = board.target_alternatives(target);
+        if alternatives.is_empty() {
+            return Err(INVALID_TARGET);
+        }
+        let area: usize = board.pieces().iter().map(|p| p.tile.len() * p.count).sum();
+
+        let mut rng = self.rng();
+        for (encoding, avoid_points) in alternatives.iter().enumerate() {
+            let mut active_board = ActiveBoard::from_custom(board, avoid_points, &mut rng);
+            let open_cells = active_board.open_coor_count();
+            match board.solve_mode() {
+                SolveMode::UseAllPieces if area <= open_cells => active_board.set_max_gaps(open_cells - area),
+                _ => {
+                    if !check_area(board, open_cells) {
+                        return Err(AREA_MISMATCH);
+                    }
+                }
+            }
+
+            // the board is left as it was, so the gaps are the open cells none of the pieces cover
+            if let Some(mut pieces) = self.solve(&mut active_board, &mut rng)? {
+                pieces.sort_by_key(|placed| (placed.piece, placed.copy));
+                let covered: HashSet<Coordinate> = pieces.iter().flat_map(|placed| placed.tile.iter().copied()).collect();
+                return Ok(Solution {
+                    pieces,
+                    target: sorted(avoid_points),
+                    encoding,
+                    gaps: active_board.open_coors().into_iter().filter(|coor| !covered.contains(coor)).collect(),
+                });
+            }
+        }
+
+        Err(NO_SOLUTION_FOUND)
+    }
+
+    // count every solution for the board, over every way it can show the target; identical copies of
+    // a piece are never told apart, so swapping two of them does not count as a new solution. A way
+    // the pieces can't fill has none, but if none of them fits it's an area mismatch, the same as for run
+    pub fn count(&self, target: &Target, board: &dyn CustomBoard) -> Result<usize, u32> {
+        let alternatives = board.target_alternatives(target);
+        if alternatives.is_empty() {
+            return Err(INVALID_TARGET);
+        }
+
+        let mut rng = self.rng();
+        let mut found = 0;
+        let mut fits = false;
+        for avoid_points in alternatives.iter() {
+            let mut active_board = ActiveBoard::from_custom(board, avoid_points, &mut rng);
+            if check_area(board, active_board.open_coor_count()) {
+                fits = true;
+                found += self.count_solutions(&mut active_board)?;
+            }
+        }
+        match fits {
+            true => Ok(found),
+            false => Err(AREA_MISMATCH),
+        }
+    }
+
+    // every solution for the target, over every way the board can show it, handed to visit one at a
+    // time (ordered by piece, the same as run's) until it returns false; returns how many were handed over
+    #[allow(dead_code)]
+    pub fn each(
+        &self,
+        target: &Target,
+        board: &dyn CustomBoard,
+        visit: &mut dyn FnMut(Solution) -> bool,
+    ) -> Result<usize, u32> {
+        self.each_of(target, board, false, visit)
+    }
+
+    // like each, but leaving gaps the way run_leaving_gaps does, so every way of placing the pieces
+    // (and so every set of gaps) is handed over
+    #[allow(dead_code)]
+    pub fn each_leaving_gaps(
+        &self,
+        target: &Target,
+        board: &dyn CustomBoard,
+        visit: &mut dyn FnMut(Solution) -> bool,
+    ) -> Result<usize, u32> {
+        self.each_of(target, board, true, visit)
+    }
+
+    // the cells every solution leaves uncovered besides the target (see run_leaving_gaps), and one of the
+    // solutions. A cell no piece fits on is always one of them; any other cell is ruled out as soon as a
+    // solution covers it, so the search only has to run to the end when a cell stays open for a subtler reason
+    #[allow(dead_code)]
+    pub fn forced_gaps(&self, target: &Target, board: &dyn CustomBoard) -> Result<(Solution, Vec<Coordinate>), u32> {
+        let alternatives = board.target_alternatives(target);
+        let mut rng = self.rng();
+        let mut unreachable: Option<HashSet<Coordinate>> = None;
+        for avoid_points in alternatives.iter() {
+            let mut active_board = ActiveBoard::from_custom(board, avoid_points, &mut rng);
+            let reachable: HashSet<Coordinate> =
+                active_board.placements().into_iter().flat_map(|placement| placement.cells).collect();
+            let here: HashSet<Coordinate> =
+                active_board.open_coors().into_iter().filter(|coor| !reachable.contains(coor)).collect();
+            unreachable = Some(match unreachable {
+                Some(cells) => &cells & &here,
+                None => here,
+            });
+        }
+        let unreachable = unreachable.unwrap_or_default();
+
+        let mut first: Option<Solution> = None;
+        let mut forced: Vec<Coordinate> = Vec::new();
+        self.each_leaving_gaps(target, board, &mut |solution| {
+            match first {
+                Some(_) => forced.retain(|coor| solution.gaps.contains(coor)),
+                None => forced = solution.gaps.clone(),
+            }
+            first.get_or_insert(solution);
+            !forced.iter().all(|coor| unreachable.contains(coor))
+        })?;
+
+        match first {
+            Some(solution) => {
+                forced.sort_by_key(|coor| (coor.y, coor.x));
+                Ok((solution, forced))
+            }
+            None => Err(NO_SOLUTION_FOUND),
+        }
+    }
+
+    fn each_of(
+        &self,
+        target: &Target,
+        board: &dyn CustomBoard,
+        leave_gaps: bool,
+        visit: &mut dyn FnMut(Solution) -> bool,
+    ) -> Result<usize, u32> {
+        let alternatives = board.target_alternatives(target);
+        if alternatives.is_empty() {
+            return Err(INVALID_TARGET);
+        }
+        let area: usize = board.pieces().iter().map(|p| p.tile.len() * p.count).sum();
+
+        let mut rng = self.rng();
+        let mut found = 0;
+        let mut fits = false;
+        for (encoding, avoid_points) in alternatives.iter().enumerate() {
+            let mut active_board = ActiveBoard::from_custom(board, avoid_points, &mut rng);
+            let open_cells = active_board.open_coor_count();
+            match board.solve_mode() {
+                SolveMode::UseAllPieces if leave_gaps && area <= open_cells => {
+                    active_board.set_max_gaps(open_cells - area)
+                }
+                _ if check_area(board, open_cells) => {}
+                _ if leave_gaps => return Err(AREA_MISMATCH),
+                _ => continue, // no solutions here, but if no way fits it's an area mismatch, as for run
+            }
+            fits = true;
+
+            // the gaps are the open cells none of the pieces cover, the same as run_leaving_gaps (exact
+            // cover never puts anything on the board)
+            let open = active_board.open_coors();
+            let mut keep_going = true;
+            let mut hand_over = |mut pieces: Vec<PlacedPiece>| -> bool {
+                pieces.sort_by_key(|placed| (placed.piece, placed.copy));
+                let covered: HashSet<Coordinate> = pieces.iter().flat_map(|placed| placed.tile.iter().copied()).collect();
+                found += 1;
+                keep_going = visit(Solution {
+                    pieces,
+                    target: sorted(avoid_points),
+                    encoding,
+                    gaps: open.iter().filter(|coor| !covered.contains(coor)).copied().collect(),
+                });
+                keep_going
+            };
+            match self.backend {
+                Backend::Backtracking => {
+                    each_solution(&mut active_board, &mut Vec::new(), &mut |active_board, steps| {
+                        hand_over(placed_steps(active_board, steps))
+                    });
+                }
+                Backend::ExactCover => {
+                    let placements = exact_cover_placements(&mut active_board, None)?;
+                    exact_cover_of(&active_board, &placements)
+                        .each(&mut |rows| hand_over(placed_pieces(&active_board, &placements, rows)));
+                }
+            }
+            if !keep_going {
+                break;
+            }
+        }
+        match fits {
+            true => Ok(found),
+            false => Err(AREA_MISMATCH),
+        }
+    }
+
+    // fill a 3d container with polycubes; the solution is ordered by piece (and copy)
+    pub fn pack(&self, puzzle: &dyn PolycubePuzzle) -> Result<Vec<PlacedCubes>, u32> {
+        let mut active_box = ActiveBox::from_puzzle(puzzle);
+        let area: usize = puzzle.pieces().iter().map(|p| p.cubes.len() * p.count).sum();
+        if area != active_box.open_cell_count() {
+            return Err(AREA_MISMATCH);
+        }
+
+        match self.solve(&mut active_box, &mut self.rng())? {
+            Some(mut solution) => {
+                solution.sort_by_key(|placed| (placed.piece, placed.copy));
+                Ok(solution)
+            }
+            None => Err(NO_SOLUTION_FOUND),
+        }
+    }
+
+    // count every way to fill the container; turning a whole solution around counts as a new one
+    pub fn count_packings(&self, puzzle: &dyn PolycubePuzzle) -> Result<usize, u32> {
+        let mut active_box = ActiveBox::from_puzzle(puzzle);
+        let area: usize = puzzle.pieces().iter().map(|p| p.cubes.len() * p.count).sum();
+        if area != active_box.open_cell_count() {
+            return Err(AREA_MISMATCH);
+        }
+        self.count_solutions(&mut active_box)
+    }
+
+    // None when there is no solution
+    fn solve<B: Packing>(&self, active_board: &mut B, rng: &mut StdRng) -> Result<Option<Vec<B::Placed>>, u32> {
+        match self.backend {
+            Backend::Backtracking => Ok(solve(active_board)),
+            Backend::ExactCover => {
+                let placements = exact_cover_placements(active_board, Some(rng))?;
+                let rows = exact_cover_of(active_board, &placements).solve();
+                Ok(rows.map(|rows| placed_pieces(active_board, &placements, &rows)))
+            }
+        }
+    }
+
+    fn count_solutions<B: Packing>(&self, active_board: &mut B) -> Result<usize, u32> {
+        match self.backend {
+            Backend::Backtracking => Ok(count_solutions(active_board)),
+            Backend::ExactCover => {
+                let placements = exact_cover_placements(active_board, None)?;
+                Ok(exact_cover_of(active_board, &placements).count())
+            }
+        }
+    }
+}
+
+// which pieces of a solution to leave out, so what's shown is still a puzzle
+#[allow(dead_code)]
+pub fn hidden_pieces(piece_count: usize, hide: usize, rng: &mut impl rand::Rng) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..piece_count).collect();
+    order.shuffle(rng);
+    let mut hidden: Vec<usize> = order.into_iter().take(hide).collect();
+    hidden.sort();
+    hidden
+}
+
+#[allow(dead_code)]
+pub fn run(target: &Target, board: &dyn CustomBoard) -> Result<Solution, u32> {
+    Search::new().run(target, board)
+}
+
+#[allow(dead_code)]
+pub fn run_leaving_gaps(target: &Target, board: &dyn CustomBoard) -> Result<Solution, u32> {
+    Search::new().run_leaving_gaps(target, board)
+}
+
+#[allow(dead_code)]
+pub fn count(target: &Target, board: &dyn CustomBoard) -> Result<usize, u32> {
+    Search::new().count(target, board)
+}
+
+#[allow(dead_code)]
+pub fn pack(puzzle: &dyn PolycubePuzzle) -> Result<Vec<PlacedCubes>, u32> {
+    Search::new().pack(puzzle)
+}
+
+#[allow(dead_code)]
+pub fn count_packings(puzzle: &dyn PolycubePuzzle) -> Result<usize, u32> {
+    Search::new().count_packings(puzzle)
+}
+
+// in reading order (by row, then by column)
+fn sorted(coors: &HashSet<Coordinate>) -> Vec<Coordinate> {
+    let mut out: Vec<Coordinate> = coors.iter().copied().collect();
+    out.sort_by_key(|coor| (coor.y, coor.x));
+    out
+}
+
+// every placement on the board, shuffled when there's an rng so different searches can find
+// different solutions
+fn exact_cover_placements<B: Packing>(
+    active_board: &mut B,
+    rng: Option<&mut StdRng>,
+) -> Result<Vec<Placement<B::Coor>>, u32> {
+    // gaps and optional pieces would need columns that may be left uncovered
+    if active_board.mode() != SolveMode::UseAllPieces || active_board.max_gaps() > 0 {
+        return Err(UNSUPPORTED_BY_BACKEND);
+    }
+
+    let mut placements = active_board.placements();
+    if let Some(rng) = rng {
+        placements.shuffle(rng);
+    }
+    Ok(placements)
+}
+
+// one column per open cell and one per piece, one row per placement
+fn exact_cover_of<B: Packing>(active_board: &B, placements: &[Placement<B::Coor>]) -> ExactCover {
+    let mut columns: HashMap<B::Coor, usize> = HashMap::new();
+    let mut open_coor = active_board.get_next_open_coor(None);
+    while let Some(coor) = open_coor {
+        columns.insert(coor, columns.len());
+        open_coor = active_board.get_next_open_coor(Some(&coor));
+    }
+    let mut needed: Vec<usize> = vec![1; columns.len()];
+
+    // the area check has already made sure a piece with no placements at all means no solution
+    let mut piece_columns: HashMap<usize, usize> = HashMap::new();
+    let mut rows: Vec<Vec<usize>> = Vec::with_capacity(placements.len());
+    for placement in placements.iter() {
+        let piece_column = *piece_columns.entry(placement.set_key).or_insert_with(|| {
+            needed.push(placement.copies);
+            needed.len() - 1
+        });
+        let mut row: Vec<usize> = placement.cells.iter().map(|coor| columns[coor]).collect();
+        row.push(piece_column);
+        rows.push(row);
+    }
+
+    ExactCover::new(needed, rows)
+}
+
+// copies of a piece are handed out in the order their placements were chosen
+fn placed_pieces<B: Packing>(active_board: &B, placements: &[Placement<B::Coor>], rows: &[usize]) -> Vec<B::Placed> {
+    let mut copies_used: HashMap<usize, usize> = HashMap::new();
+    rows.iter()
+        .map(|row| {
+            let placement = &placements[*row];
+            let copy = copies_used.entry(placement.set_key).or_insert(0);
+            *copy += 1;
+            active_board.placed_piece(placement.set_key + *copy - 1, placement.orientation, &placement.tile)
+        })
+        .collect()
+}
+
+// can the pieces cover exactly (or, with optional pieces, at least) the open cells?
+pub fn check_area(board: &dyn CustomBoard, open_cells: usize) -> bool {
+    let pieces = board.pieces();
+    let area = |i: usize| pieces[i].tile.len() * pieces[i].count;
+    let total_area: usize = (0..pieces.len()).map(area).sum();
+
+    match board.solve_mode() {
+        SolveMode::UseAllPieces => total_area == open_cells,
+        SolveMode::CoverBoard => {
+            let groups = board.piece_groups();
+            let in_group = |i: usize| groups.iter().any(|g| g.pieces.contains(&i));
+
+            // the least area that has to be placed: required pieces, plus the smallest picks of each group
+            let mut min_area: usize = (0..pieces.len())
+                .filter(|i| !in_group(*i) && pieces[*i].usage == Usage::Required)
+                .map(area)
+                .sum();
+            for group in groups.iter() {
+                let mut sizes: Vec<usize> = group
+                    .pieces
+                    .iter()
+                    .flat_map(|i| std::iter::repeat_n(pieces[*i].tile.len(), pieces[*i].count))
+                    .collect();
+                sizes.sort();
+                min_area += sizes.iter().take(group.pick).sum::<usize>();
+            }
+
+            min_area <= open_cells && open_cells <= total_area
+        }
+    }
+}
+
+// a tile placed on the way to a solution
+struct Step<C> {
+    set_key: usize,
+    orientation: usize,
+    tile: Vec<C>, // moved into place
+}
+
+// handed the board and the steps to each solution; false stops the search
+type Visit<'a, B> = dyn FnMut(&B, &[Step<<B as Packing>::Coor>]) -> bool + 'a;
+
+// the first solution the search finds
+pub fn solve<B: Packing>(active_board: &mut B) -> Option<Vec<B::Placed>> {
+    let mut first = None;
+    each_solution(active_board, &mut Vec::new(), &mut |active_board, steps| {
+        first = Some(placed_steps(active_board, steps));
+        false
+    });
+    first
+}
+
+// every solution, counted
+pub fn count_solutions<B: Packing>(active_board: &mut B) -> usize {
+    let mut found = 0;
+    each_solution(active_board, &mut Vec::new(), &mut |_, _| {
+        found += 1;
+        true
+    });
+    found
+}
+
+// try to place a tile, then recurse to place the next tile; each solution found is handed to visit,
+// until it returns false (and so does this)
+fn each_solution<B: Packing>(
+    active_board: &mut B,
+    steps: &mut Vec<Step<B::Coor>>,
+    visit: &mut Visit<B>,
+) -> bool {
+    if active_board.mode() == SolveMode::CoverBoard && active_board.is_covered() {
+        // every open cell is covered -> solved, if the right pieces were used
+        return !active_board.usage_satisfied() || visit(active_board, steps);
+    }
+
     let mut tile_set = match active_board.get_next_tile_set() {
         Some(index) => index,
         None => {
-            return true; // no unplaced tiles left -> board is solved!
+            // no unplaced tiles left -> board is solved! (unless there are still open cells to cover)
+            return active_board.mode() != SolveMode::UseAllPieces || visit(active_board, steps);
         }
     };
 
-    let islands = active_board.find_islands();
+    // islands too small to fit any remaining tiles can never be covered -> no solution possible,
+    // unless there are enough gaps allowed to leave them all open
     let smallest_area = active_board.smallest_unplaced_tile_size();
-    for island in islands {
-        if island.len() < smallest_area {
-            return false; // found an island too small to fit any remaining tiles -> no solution possible
-        }
+    let stranded: usize = active_board
+        .island_sizes()
+        .into_iter()
+        .filter(|size| *size < smallest_area)
+        .sum();
+    if stranded > active_board.max_gaps() {
+        return true;
     }
 
-    // get the first open coordinate on the board
-    let mut open_coor = active_board.get_next_open_coor(None);
+    let set_key = tile_set.key();
 
-    while let Some(coor) = open_coor {
-        // move tile set to current open_coor
-        let offset = TileHelper::calc_offset(&tile_set.tiles[0], &coor);
-        for tile in tile_set.tiles.iter_mut() {
-            TileHelper::translate(tile, &offset);
-        }
+    // get the first open coordinate on the board (or the first one after the previous copy of this tile)
+    let mut open_coor = if active_board.can_place(set_key) {
+        active_board.get_next_open_coor(tile_set.start_after())
+    } else {
+        None
+    };
 
+    while let Some(coor) = open_coor {
         // try placing each orientation of the tile set at the open coordinate
-        for tile in tile_set.tiles.iter() {
-            if active_board.place_tile(tile_set.key(), tile) {
-                // successfully placed tile, continue solving recursively (try placing the next tile)
-                if solve(active_board, solution) {
-                    // found a solution!
-                    solution.push(tile.clone());
-                    return true;
-                } else {
-                    // backtrack - remove the tile and try the next orientation
-                    active_board.remove_tile(tile_set.key(), tile);
+        for (orientation, tile) in tile_set.tiles.iter_mut().enumerate() {
+            // move the tile to current open_coor (unless that would turn it into a different shape)
+            if !active_board.move_tile(tile, &coor) {
+                continue;
+            }
+
+            if active_board.place_tile(set_key, tile) {
+                // continue solving recursively (try placing the next tile), then backtrack - remove the
+                // tile and try the next orientation
+                steps.push(Step {
+                    set_key,
+                    orientation,
+                    tile: tile.clone(),
+                });
+                let keep_going = each_solution(active_board, steps, visit);
+                steps.pop();
+                active_board.remove_tile(set_key, tile);
+                if !keep_going {
+                    return false;
                 }
             }
         }
@@ -64,5 +572,215 @@ pub fn solve(active_board: &mut ActiveBoard, solution: &mut Vec<Tile>) -> bool {
         open_coor = active_board.get_next_open_coor(Some(&coor));
     }
 
-    false
+    // optional tile: try leaving it off the board entirely
+    if active_board.can_skip(set_key) {
+        active_board.skip_tile_set(set_key);
+        let keep_going = each_solution(active_board, steps, visit);
+        active_board.unskip_tile_set(set_key);
+        if !keep_going {
+            return false;
+        }
+    }
+
+    true
+}
+
+// the pieces of a solution, in terms of the board's pieces
+fn placed_steps<B: Packing>(active_board: &B, steps: &[Step<B::Coor>]) -> Vec<B::Placed> {
+    steps
+        .iter()
+        .map(|step| active_board.placed_piece(step.set_key, step.orientation, &step.tile))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{AllTime, Piece, PieceGroup, Tile, c};
+    use crate::custom_boards::{hexagonal::HexagonalCalendarBoard, nova_scotia::NovaScotiaCalendarBoard, soma::SomaCube};
+    use crate::modified_board::ModifiedBoard;
+    use crate::schema::SolutionRecord;
+
+    // the exact-cover backend is a search of its own, so it has to find exactly what backtracking does;
+    // the seed only fixes the order pieces are tried in, so each run takes about as long
+    fn both_backends(count: impl Fn(&Search) -> Result<usize, u32>) -> (usize, usize) {
+        let search = |backend: Backend| Search::new().with_backend(backend).with_seed(Some(1));
+        let backtracking = count(&search(Backend::Backtracking)).unwrap();
+        let exact_cover = count(&search(Backend::ExactCover)).unwrap();
+        (backtracking, exact_cover)
+    }
+
+    fn count_date(board: &dyn CustomBoard, date: &str) -> (usize, usize) {
+        let target = Target::Date(AllTime::from_iso(date).unwrap());
+        both_backends(|search| search.count(&target, board))
+    }
+
+    // a w×h rectangle with no date on it, for counting tilings by hand
+    struct Rectangle {
+        width: i32,
+        height: i32,
+        pieces: Vec<Piece>,
+        groups: Vec<PieceGroup>,
+    }
+
+    impl Rectangle {
+        fn new(width: i32, height: i32, pieces: Vec<Piece>) -> Rectangle {
+            Rectangle {
+                width,
+                height,
+                pieces,
+                groups: vec![],
+            }
+        }
+
+        fn with_group(mut self, pieces: Vec<usize>, pick: usize) -> Rectangle {
+            self.groups.push(PieceGroup { pieces, pick });
+            self
+        }
+
+        fn count(&self, backend: Backend) -> Result<usize, u32> {
+            let target = Target::Coordinates(HashSet::new());
+            Search::new().with_backend(backend).with_seed(Some(1)).count(&target, self)
+        }
+    }
+
+    impl CustomBoard for Rectangle {
+        fn name(&self) -> String {
+            format!("{}×{}", self.width, self.height)
+        }
+
+        fn tiles(&self) -> Vec<Tile> {
+            self.pieces.iter().map(|piece| piece.tile.clone()).collect()
+        }
+
+        fn pieces(&self) -> Vec<Piece> {
+            self.pieces.clone()
+        }
+
+        fn piece_groups(&self) -> Vec<PieceGroup> {
+            self.groups.clone()
+        }
+
+        fn coors(&self) -> Vec<Coordinate> {
+            (0..self.height).flat_map(|y| (0..self.width).map(move |x| c(x, y))).collect()
+        }
+
+        fn point_in_time(&self, _pit: &AllTime) -> Option<HashSet<Coordinate>> {
+            None
+        }
+    }
+
+    fn monomino() -> Piece {
+        Piece::new(vec![c(0, 0)])
+    }
+
+    fn domino() -> Piece {
+        Piece::new(vec![c(0, 0), c(1, 0)])
+    }
+
+    fn tromino() -> Piece {
+        Piece::new(vec![c(0, 0), c(1, 0), c(2, 0)])
+    }
+
+    // a 2×3 rectangle has 3 domino tilings; three different dominoes can be arranged in each of
+    // them 3! ways, but three copies of the same one can't be told apart
+    #[test]
+    fn copies_of_a_piece_are_not_told_apart() {
+        let copies = Rectangle::new(2, 3, vec![domino().with_count(3)]);
+        let count = |backend| copies.count(backend).unwrap();
+        assert_eq!((count(Backend::Backtracking), count(Backend::ExactCover)), (3, 3));
+
+        let different = Rectangle::new(2, 3, vec![domino(), domino(), domino()]);
+        let count = |backend| different.count(backend).unwrap();
+        assert_eq!((count(Backend::Backtracking), count(Backend::ExactCover)), (18, 18));
+
+        // two copies and a third domino: the odd one out can be any of the three in a tiling
+        let mixed = Rectangle::new(2, 3, vec![domino().with_count(2), domino()]);
+        assert_eq!(mixed.count(Backend::Backtracking), Ok(9));
+    }
+
+    // on a 1×4 strip the domino can go in 3 places, and the two copies of the monomino fill the rest
+    // the same way whichever is where
+    #[test]
+    fn optional_pieces_can_be_left_out() {
+        let strip = Rectangle::new(4, 1, vec![domino(), monomino().with_usage(Usage::Optional).with_count(2)]);
+        assert_eq!(strip.count(Backend::Backtracking), Ok(3));
+
+        // on a 1×5 strip only the tromino fits beside the domino, on either side of it
+        let strip = Rectangle::new(
+            5,
+            1,
+            vec![domino(), monomino().with_usage(Usage::Optional), tromino().with_usage(Usage::Optional)],
+        );
+        assert_eq!(strip.count(Backend::Backtracking), Ok(2));
+        assert_eq!(strip.count(Backend::ExactCover), Err(UNSUPPORTED_BY_BACKEND));
+    }
+
+    // exactly pick of the group's pieces are used: on a 1×4 strip, the monomino and the tromino
+    // (either way round), and never the domino with them
+    #[test]
+    fn a_group_uses_exactly_pick_pieces() {
+        let strip = Rectangle::new(4, 1, vec![monomino(), domino(), tromino()]);
+        assert_eq!(strip.with_group(vec![0, 1, 2], 2).count(Backend::Backtracking), Ok(2));
+
+        // no one of them covers the strip by itself
+        let strip = Rectangle::new(4, 1, vec![monomino(), domino(), tromino()]);
+        assert_eq!(strip.with_group(vec![0, 1, 2], 1).count(Backend::Backtracking), Ok(0));
+
+        // copies count towards the pick: two monominoes and the domino, in 3 orders
+        let strip = Rectangle::new(4, 1, vec![monomino().with_count(2), domino(), tromino()]);
+        assert_eq!(strip.with_group(vec![0, 1, 2], 3).count(Backend::Backtracking), Ok(3));
+    }
+
+    #[test]
+    fn backends_agree_on_a_square_board() {
+        assert_eq!(count_date(&NovaScotiaCalendarBoard {}, "2026-03-05"), (32, 32));
+    }
+
+    #[test]
+    fn backends_agree_on_a_hexagonal_board() {
+        assert_eq!(count_date(&HexagonalCalendarBoard {}, "2026-03-05"), (695, 695));
+    }
+
+    // every solution written out as it's streamed, so the records can't depend on the backend either
+    #[test]
+    fn backends_write_the_same_records() {
+        let board = NovaScotiaCalendarBoard {};
+        let target = Target::Date(AllTime::from_iso("2026-03-05").unwrap());
+        let records = |backend: Backend| -> Vec<String> {
+            let mut records: Vec<String> = Vec::new();
+            Search::new()
+                .with_backend(backend)
+                .with_seed(Some(1))
+                .each(&target, &board, &mut |solution| {
+                    let record = SolutionRecord::new(&board, &target, &solution);
+                    records.push(serde_json::to_string(&record).unwrap());
+                    true
+                })
+                .unwrap();
+            records.sort();
+            records
+        };
+        let backtracking = records(Backend::Backtracking);
+        assert_eq!(backtracking.len(), 32);
+        assert_eq!(backtracking, records(Backend::ExactCover));
+    }
+
+    // a missing piece leaves more open cells than the pieces fill, which is an error and not zero solutions
+    #[test]
+    fn counting_reports_an_area_mismatch() {
+        let nova_scotia = NovaScotiaCalendarBoard {};
+        let board = ModifiedBoard::new(&nova_scotia).without_piece(0);
+        let target = Target::Date(AllTime::from_iso("2026-03-05").unwrap());
+        for backend in [Backend::Backtracking, Backend::ExactCover] {
+            let search = Search::new().with_backend(backend);
+            assert_eq!(search.count(&target, &board), Err(AREA_MISMATCH));
+            assert_eq!(search.each(&target, &board, &mut |_| true), Err(AREA_MISMATCH));
+        }
+    }
+
+    #[test]
+    fn backends_agree_on_a_3d_puzzle() {
+        assert_eq!(both_backends(|search| search.count_packings(&SomaCube {})), (11520, 11520));
+    }
 }
@@ -0,0 +1,542 @@
+use std::fmt::Write;
+
+use crate::common::{Coordinate, CustomBoard};
+use crate::drawing::{Point, center, chains, loops, offset, outline, signed_area};
+
+// x, y and z in millimetres, with z pointing up out of the print bed
+pub type Point3 = (f64, f64, f64);
+
+// the depth the labels are engraved into the floor of the tray
+const LABEL_DEPTH: f64 = 0.6;
+
+// every piece of a board as a solid to 3d print, and a tray to keep them in: a plate with the board's
+// shape sunk into it and the labels engraved into the floor of the recess. Pieces come out smaller by
+// half the clearance on every side, so they fit the tray (and each other) without forcing
+pub struct StlModel {
+    cell_size: f64, // in millimetres, between neighbouring cells
+    height: f64,    // how thick the pieces are
+    clearance: f64, // the gap between neighbouring pieces
+    floor: f64,     // how thick the tray is under the pieces
+}
+
+// which parts to make
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    Tray,
+    Pieces,
+    Both, // the pieces lie next to the tray
+}
+
+impl Part {
+    pub fn from_name(name: &str) -> Option<Part> {
+        match name.to_lowercase().as_str() {
+            "tray" => Some(Part::Tray),
+            "pieces" => Some(Part::Pieces),
+            "both" => Some(Part::Both),
+            _ => None,
+        }
+    }
+}
+
+// the triangles of the surface, each one counter-clockwise seen from outside
+pub struct Mesh {
+    pub name: String,
+    pub triangles: Vec<[Point3; 3]>,
+}
+
+impl StlModel {
+    pub fn new() -> StlModel {
+        StlModel {
+            cell_size: 20.0,
+            height: 6.0,
+            clearance: 0.3,
+            floor: 2.0,
+        }
+    }
+
+    pub fn with_cell_size(mut self, cell_size: f64) -> StlModel {
+        self.cell_size = cell_size;
+        self
+    }
+
+    pub fn with_height(mut self, height: f64) -> StlModel {
+        self.height = height;
+        self
+    }
+
+    pub fn with_clearance(mut self, clearance: f64) -> StlModel {
+        self.clearance = clearance;
+        self
+    }
+
+    pub fn cell_size(&self) -> f64 {
+        self.cell_size
+    }
+
+    pub fn clearance(&self) -> f64 {
+        self.clearance
+    }
+
+    pub fn mesh(&self, board: &dyn CustomBoard, part: Part) -> Mesh {
+        let mut triangles: Vec<[Point3; 3]> = Vec::new();
+        let mut left = 0.0;
+        if part != Part::Pieces {
+            left = self.tray(board, &mut triangles) + self.cell_size / 2.0;
+        }
+        if part != Part::Tray {
+            self.pieces(board, left, &mut triangles);
+        }
+        Mesh {
+            name: board.name(),
+            triangles,
+        }
+    }
+
+    // the cells in millimetres, with y pointing up (so the board doesn't come out mirrored); flipping
+    // them turns their corners round the other way, so they're put back counter-clockwise
+    fn cell(&self, board: &dyn CustomBoard, coor: &Coordinate) -> Vec<Point> {
+        board
+            .lattice()
+            .cell_polygon(coor)
+            .into_iter()
+            .rev()
+            .map(|(x, y)| (x * self.cell_size, -y * self.cell_size))
+            .collect()
+    }
+
+    // every piece of the kit, in rows about as wide as the tray, from x = left
+    fn pieces(&self, board: &dyn CustomBoard, left: f64, triangles: &mut Vec<[Point3; 3]>) {
+        let spacing = self.cell_size / 2.0;
+        let board_cells: Vec<Vec<Point>> = board.coors().iter().map(|coor| self.cell(board, coor)).collect();
+        let (min_x, _, max_x, _) = bounds(&board_cells);
+        let row_width = max_x - min_x + self.cell_size;
+        let (mut x, mut y, mut row_height) = (0.0, 0.0, 0.0_f64);
+        for piece in board.pieces().iter() {
+            for _ in 0..piece.count {
+                let cells: Vec<Vec<Point>> = piece.tile.iter().map(|coor| self.cell(board, coor)).collect();
+                let winding = cells.first().map(|cell| signed_area(cell)).unwrap_or(1.0);
+                let (min_x, min_y, max_x, max_y) = bounds(&cells);
+                let (width, height) = (max_x - min_x, max_y - min_y);
+                if x > 0.0 && x + width > row_width {
+                    (x, y, row_height) = (0.0, y + row_height + spacing, 0.0);
+                }
+
+                let shrunk: Vec<Vec<Point>> = loops(&outline(&cells))
+                    .iter()
+                    .map(|path| offset(path, -self.clearance / 2.0, winding))
+                    .map(|path| path.iter().map(|(px, py)| (px - min_x + left + x, py - min_y + y)).collect())
+                    .collect();
+                for (outer, holes) in regions(shrunk) {
+                    for triangle in triangulate(&outer, &holes) {
+                        triangles.push(flat(triangle, self.height));
+                        triangles.push(facing_down(flat(triangle, 0.0)));
+                    }
+                    for path in std::iter::once(&outer).chain(holes.iter()) {
+                        for (from, to) in edges(path) {
+                            wall(triangles, from, to, 0.0, self.height);
+                        }
+                    }
+                }
+
+                x += width + spacing;
+                row_height = row_height.max(height);
+            }
+        }
+    }
+
+    // the tray, from x = 0; returns how wide it is
+    fn tray(&self, board: &dyn CustomBoard, triangles: &mut Vec<[Point3; 3]>) -> f64 {
+        let coors = board.coors();
+        let cells: Vec<Vec<Point>> = coors.iter().map(|coor| self.cell(board, coor)).collect();
+        let (min_x, min_y, max_x, max_y) = bounds(&cells);
+        let border = self.cell_size / 2.0;
+        let cells: Vec<Vec<Point>> = cells
+            .into_iter()
+            .map(|cell| cell.into_iter().map(|(x, y)| (x - min_x + border, y - min_y + border)).collect())
+            .collect();
+        let (width, height) = (max_x - min_x + 2.0 * border, max_y - min_y + 2.0 * border);
+        let (floor, top) = (self.floor, self.floor + self.height / 2.0);
+
+        // the outside: the bottom, the sides, and the rim around the recess
+        let plate = vec![(0.0, 0.0), (width, 0.0), (width, height), (0.0, height)];
+        for triangle in triangulate(&plate, &[]) {
+            triangles.push(facing_down(flat(triangle, 0.0)));
+        }
+        for (from, to) in edges(&plate) {
+            wall(triangles, from, to, 0.0, top);
+        }
+        let edges_of_board = outline(&cells);
+        let mut rim = vec![plate];
+        rim.extend(chains(&edges_of_board));
+        for (outer, holes) in regions(rim) {
+            for triangle in triangulate(&outer, &holes) {
+                triangles.push(flat(triangle, top));
+            }
+        }
+
+        // the recess: its walls face in, towards the cells
+        for (from, to) in edges_of_board {
+            wall(triangles, to, from, floor, top);
+        }
+        let labels = board.labels();
+        for (coor, cell) in coors.iter().zip(cells.iter()) {
+            match labels.get(coor) {
+                Some(label) if !label.is_empty() => self.labelled_floor(cell, label, floor, triangles),
+                _ => {
+                    for triangle in triangulate(cell, &[]) {
+                        triangles.push(flat(triangle, floor));
+                    }
+                }
+            }
+        }
+        width
+    }
+
+    // the floor of a cell with its label engraved in the middle, in the pixel font
+    fn labelled_floor(&self, cell: &[Point], label: &str, floor: f64, triangles: &mut Vec<[Point3; 3]>) {
+        let glyphs: Vec<[u8; 5]> = label.chars().map(glyph).collect();
+        let (columns, rows) = (glyphs.len() * 4 - 1, 5);
+        let lit = |column: usize, row: usize| -> bool {
+            if column % 4 == 3 {
+                return false; // between letters
+            }
+            glyphs[column / 4][row] & (0b100 >> (column % 4)) != 0
+        };
+
+        // as big as fits in the circle inside the cell, with a little room to spare
+        let (center_x, center_y) = center(cell);
+        let inside = edges(cell)
+            .map(|(from, to)| distance_to_line((center_x, center_y), from, to))
+            .fold(f64::INFINITY, f64::min);
+        let pixel = 1.6 * inside / ((columns * columns + rows * rows) as f64).sqrt();
+        let (left, top) = (center_x - pixel * columns as f64 / 2.0, center_y + pixel * rows as f64 / 2.0);
+        let corner = |column: usize, row: usize| -> Point { (left + column as f64 * pixel, top - row as f64 * pixel) };
+
+        // the cell around the label, with the label's box as a (clockwise) hole, cornered wherever the
+        // pixels are
+        let mut hole: Vec<Point> = Vec::new();
+        hole.extend((0..columns).map(|column| corner(column, 0)));
+        hole.extend((0..rows).map(|row| corner(columns, row)));
+        hole.extend((0..columns).map(|column| corner(columns - column, rows)));
+        hole.extend((0..rows).map(|row| corner(0, rows - row)));
+        for triangle in triangulate(cell, &[hole]) {
+            triangles.push(flat(triangle, floor));
+        }
+
+        // then the box, pixel by pixel: each lit one is a dot sunk into the floor, a little smaller than
+        // the pixel, so dots that only meet at a corner (as in A, or X) stay apart
+        let depth = floor - LABEL_DEPTH;
+        let inset = pixel / 10.0;
+        for row in 0..rows {
+            for column in 0..columns {
+                let square = [
+                    corner(column, row + 1),
+                    corner(column + 1, row + 1),
+                    corner(column + 1, row),
+                    corner(column, row),
+                ];
+                if !lit(column, row) {
+                    for triangle in triangulate(&square, &[]) {
+                        triangles.push(flat(triangle, floor));
+                    }
+                    continue;
+                }
+                let dot = [
+                    (square[0].0 + inset, square[0].1 + inset),
+                    (square[1].0 - inset, square[1].1 + inset),
+                    (square[2].0 - inset, square[2].1 - inset),
+                    (square[3].0 + inset, square[3].1 - inset),
+                ];
+                for i in 0..4 {
+                    let next = (i + 1) % 4;
+                    triangles.push(flat([square[i], square[next], dot[next]], floor));
+                    triangles.push(flat([square[i], dot[next], dot[i]], floor));
+                    wall(triangles, dot[next], dot[i], depth, floor);
+                }
+                for triangle in triangulate(&dot, &[]) {
+                    triangles.push(flat(triangle, depth));
+                }
+            }
+        }
+    }
+}
+
+impl Mesh {
+    pub fn ascii(&self) -> String {
+        let name: String = self.name.chars().filter(|ch| ch.is_ascii_alphanumeric()).collect();
+        let mut stl = String::new();
+        let _ = writeln!(stl, "solid {}", name);
+        for triangle in self.triangles.iter() {
+            let (x, y, z) = normal(triangle);
+            let _ = writeln!(stl, "  facet normal {:.6} {:.6} {:.6}", x, y, z);
+            let _ = writeln!(stl, "    outer loop");
+            for (x, y, z) in triangle.iter() {
+                let _ = writeln!(stl, "      vertex {:.4} {:.4} {:.4}", x, y, z);
+            }
+            let _ = writeln!(stl, "    endloop");
+            let _ = writeln!(stl, "  endfacet");
+        }
+        let _ = writeln!(stl, "endsolid {}", name);
+        stl
+    }
+
+    // an 80 byte header, the number of triangles, then for each one its normal, its corners and two
+    // unused bytes, all little-endian
+    pub fn binary(&self) -> Vec<u8> {
+        let mut stl: Vec<u8> = Vec::with_capacity(84 + self.triangles.len() * 50);
+        let mut header = format!("puzzle-a-day: {}", self.name).into_bytes();
+        header.resize(80, b' ');
+        stl.extend(header);
+        stl.extend((self.triangles.len() as u32).to_le_bytes());
+        for triangle in self.triangles.iter() {
+            for (x, y, z) in std::iter::once(normal(triangle)).chain(triangle.iter().copied()) {
+                for value in [x, y, z] {
+                    stl.extend((value as f32).to_le_bytes());
+                }
+            }
+            stl.extend([0, 0]);
+        }
+        stl
+    }
+}
+
+// 3 pixels wide and 5 high, a row to a byte, leftmost pixel highest; letters are all capitals, and
+// anything without a glyph is left blank
+fn glyph(ch: char) -> [u8; 5] {
+    match ch.to_ascii_uppercase() {
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b111, 0b001, 0b111, 0b100, 0b111],
+        '3' => [0b111, 0b001, 0b111, 0b001, 0b111],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b111, 0b001, 0b111],
+        '6' => [0b111, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b001, 0b001, 0b001],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b111],
+        'A' => [0b010, 0b101, 0b111, 0b101, 0b101],
+        'B' => [0b110, 0b101, 0b110, 0b101, 0b110],
+        'C' => [0b011, 0b100, 0b100, 0b100, 0b011],
+        'D' => [0b110, 0b101, 0b101, 0b101, 0b110],
+        'E' => [0b111, 0b100, 0b110, 0b100, 0b111],
+        'F' => [0b111, 0b100, 0b110, 0b100, 0b100],
+        'G' => [0b011, 0b100, 0b101, 0b101, 0b011],
+        'H' => [0b101, 0b101, 0b111, 0b101, 0b101],
+        'I' => [0b111, 0b010, 0b010, 0b010, 0b111],
+        'J' => [0b001, 0b001, 0b001, 0b101, 0b010],
+        'K' => [0b101, 0b101, 0b110, 0b101, 0b101],
+        'L' => [0b100, 0b100, 0b100, 0b100, 0b111],
+        'M' => [0b101, 0b111, 0b111, 0b101, 0b101],
+        'N' => [0b110, 0b101, 0b101, 0b101, 0b101],
+        'O' => [0b010, 0b101, 0b101, 0b101, 0b010],
+        'P' => [0b110, 0b101, 0b110, 0b100, 0b100],
+        'Q' => [0b010, 0b101, 0b101, 0b110, 0b011],
+        'R' => [0b110, 0b101, 0b110, 0b101, 0b101],
+        'S' => [0b011, 0b100, 0b010, 0b001, 0b110],
+        'T' => [0b111, 0b010, 0b010, 0b010, 0b010],
+        'U' => [0b101, 0b101, 0b101, 0b101, 0b111],
+        'V' => [0b101, 0b101, 0b101, 0b101, 0b010],
+        'W' => [0b101, 0b101, 0b111, 0b111, 0b101],
+        'X' => [0b101, 0b101, 0b010, 0b101, 0b101],
+        'Y' => [0b101, 0b101, 0b010, 0b010, 0b010],
+        'Z' => [0b111, 0b001, 0b010, 0b100, 0b111],
+        _ => [0; 5],
+    }
+}
+
+fn flat([a, b, c]: [Point; 3], z: f64) -> [Point3; 3] {
+    [(a.0, a.1, z), (b.0, b.1, z), (c.0, c.1, z)]
+}
+
+fn facing_down([a, b, c]: [Point3; 3]) -> [Point3; 3] {
+    [a, c, b]
+}
+
+// the upright face over the edge, from bottom to top, facing to the right of from -> to
+fn wall(triangles: &mut Vec<[Point3; 3]>, from: Point, to: Point, bottom: f64, top: f64) {
+    let (a0, b0) = ((from.0, from.1, bottom), (to.0, to.1, bottom));
+    let (a1, b1) = ((from.0, from.1, top), (to.0, to.1, top));
+    triangles.push([a0, b0, b1]);
+    triangles.push([a0, b1, a1]);
+}
+
+fn normal([a, b, c]: &[Point3; 3]) -> Point3 {
+    let (u, v) = ((b.0 - a.0, b.1 - a.1, b.2 - a.2), (c.0 - a.0, c.1 - a.1, c.2 - a.2));
+    let (x, y, z) = (u.1 * v.2 - u.2 * v.1, u.2 * v.0 - u.0 * v.2, u.0 * v.1 - u.1 * v.0);
+    let length = (x * x + y * y + z * z).sqrt();
+    if length == 0.0 { (0.0, 0.0, 0.0) } else { (x / length, y / length, z / length) }
+}
+
+fn edges(path: &[Point]) -> impl Iterator<Item = (Point, Point)> + '_ {
+    (0..path.len()).map(|i| (path[i], path[(i + 1) % path.len()]))
+}
+
+fn bounds(polygons: &[Vec<Point>]) -> (f64, f64, f64, f64) {
+    let points = || polygons.iter().flatten();
+    (
+        points().map(|(x, _)| *x).fold(f64::INFINITY, f64::min),
+        points().map(|(_, y)| *y).fold(f64::INFINITY, f64::min),
+        points().map(|(x, _)| *x).fold(f64::NEG_INFINITY, f64::max),
+        points().map(|(_, y)| *y).fold(f64::NEG_INFINITY, f64::max),
+    )
+}
+
+fn distance_to_line(point: Point, from: Point, to: Point) -> f64 {
+    let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+    ((point.0 - from.0) * dy - (point.1 - from.1) * dx).abs() / (dx * dx + dy * dy).sqrt()
+}
+
+// loops sorted into shapes: a loop inside an even number of others is the outside of a shape
+// (counter-clockwise), and one inside an odd number is a hole (clockwise) in the innermost of them
+fn regions(paths: Vec<Vec<Point>>) -> Vec<(Vec<Point>, Vec<Vec<Point>>)> {
+    let paths: Vec<Vec<Point>> = paths.into_iter().filter(|path| path.len() >= 3).collect();
+    // the middle of an edge can't be on any other loop
+    let probes: Vec<Point> = paths.iter().map(|path| midpoint(path[0], path[1])).collect();
+    let containing: Vec<Vec<usize>> = (0..paths.len())
+        .map(|i| (0..paths.len()).filter(|j| *j != i && contains(&paths[*j], probes[i])).collect())
+        .collect();
+    let depth = |i: usize| containing[i].len();
+    let turned = |path: &Vec<Point>, clockwise: bool| -> Vec<Point> {
+        let mut path = path.clone();
+        if (signed_area(&path) < 0.0) != clockwise {
+            path.reverse();
+        }
+        path
+    };
+
+    let mut regions: Vec<(usize, Vec<Point>, Vec<Vec<Point>>)> = (0..paths.len())
+        .filter(|i| depth(*i) % 2 == 0)
+        .map(|i| (i, turned(&paths[i], false), vec![]))
+        .collect();
+    for i in (0..paths.len()).filter(|i| depth(*i) % 2 == 1) {
+        let parent = containing[i].iter().find(|j| depth(**j) + 1 == depth(i));
+        if let Some(region) = regions.iter_mut().find(|(outer, _, _)| Some(outer) == parent) {
+            region.2.push(turned(&paths[i], true));
+        }
+    }
+    regions.into_iter().map(|(_, outer, holes)| (outer, holes)).collect()
+}
+
+fn midpoint(a: Point, b: Point) -> Point {
+    ((a.0 + b.0) / 2.0, (a.1 + b.1) / 2.0)
+}
+
+// even-odd, so it works for any loop
+fn contains(path: &[Point], (x, y): Point) -> bool {
+    let mut inside = false;
+    for (a, b) in edges(path) {
+        if (a.1 > y) != (b.1 > y) && x < a.0 + (y - a.1) * (b.0 - a.0) / (b.1 - a.1) {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+// a shape with holes as triangles, by cutting off one ear at a time: the holes are first joined to the
+// outside by a pair of edges there and back, so it's all one loop
+fn triangulate(outer: &[Point], holes: &[Vec<Point>]) -> Vec<[Point; 3]> {
+    let mut path: Vec<Point> = outer.to_vec();
+    let mut holes: Vec<&Vec<Point>> = holes.iter().filter(|hole| hole.len() >= 3).collect();
+    let rightmost = |hole: &Vec<Point>| hole.iter().map(|(x, _)| *x).fold(f64::NEG_INFINITY, f64::max);
+    holes.sort_by(|a, b| rightmost(b).total_cmp(&rightmost(a)));
+    for (i, hole) in holes.iter().enumerate() {
+        path = bridged(&path, hole, &holes[i + 1..]);
+    }
+    ear_clipped(path)
+}
+
+// the hole spliced into the loop, from its rightmost corner to the nearest corner of the loop that
+// can be reached without crossing anything
+fn bridged(path: &[Point], hole: &[Point], others: &[&Vec<Point>]) -> Vec<Point> {
+    let start = (0..hole.len()).max_by(|a, b| hole[*a].0.total_cmp(&hole[*b].0)).unwrap_or(0);
+    let from = hole[start];
+    let blocked = |to: Point| -> bool {
+        let crosses = |loop_: &[Point]| edges(loop_).any(|(a, b)| crossing(from, to, a, b));
+        crosses(path) || crosses(hole) || others.iter().any(|other| crosses(other))
+    };
+    let distance = |to: &Point| (to.0 - from.0).powi(2) + (to.1 - from.1).powi(2);
+    let mut candidates: Vec<usize> = (0..path.len()).collect();
+    candidates.sort_by(|a, b| distance(&path[*a]).total_cmp(&distance(&path[*b])));
+    let Some(to) = candidates.into_iter().find(|i| {
+        let middle = midpoint(from, path[*i]);
+        !blocked(path[*i]) && contains(path, middle) && !contains(hole, middle)
+    }) else {
+        return path.to_vec();
+    };
+
+    let mut joined: Vec<Point> = path[..=to].to_vec();
+    joined.extend(hole[start..].iter().copied());
+    joined.extend(hole[..=start].iter().copied());
+    joined.extend(path[to..].iter().copied());
+    joined
+}
+
+// whether the segments touch anywhere but at the ends of the first one
+fn crossing(p: Point, q: Point, a: Point, b: Point) -> bool {
+    let same = |u: Point, v: Point| (u.0 - v.0).abs() < 1e-9 && (u.1 - v.1).abs() < 1e-9;
+    if [a, b].iter().any(|end| same(*end, p) || same(*end, q)) {
+        return false;
+    }
+    let side = |u: Point, v: Point, w: Point| (v.0 - u.0) * (w.1 - u.1) - (v.1 - u.1) * (w.0 - u.0);
+    let (d1, d2) = (side(p, q, a), side(p, q, b));
+    let (d3, d4) = (side(a, b, p), side(a, b, q));
+    if ((d1 > 0.0 && d2 < 0.0) || (d1 < 0.0 && d2 > 0.0)) && ((d3 > 0.0 && d4 < 0.0) || (d3 < 0.0 && d4 > 0.0)) {
+        return true;
+    }
+    // a corner lying on the segment blocks it too
+    let on = |w: Point| {
+        side(p, q, w).abs() < 1e-9
+            && w.0 >= p.0.min(q.0) - 1e-9
+            && w.0 <= p.0.max(q.0) + 1e-9
+            && w.1 >= p.1.min(q.1) - 1e-9
+            && w.1 <= p.1.max(q.1) + 1e-9
+    };
+    on(a) || on(b)
+}
+
+// a counter-clockwise loop (which may touch itself, where holes were joined in) as triangles
+fn ear_clipped(mut path: Vec<Point>) -> Vec<[Point; 3]> {
+    let cross = |a: Point, b: Point, c: Point| (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0);
+    let same = |u: &Point, v: &Point| (u.0 - v.0).abs() < 1e-9 && (u.1 - v.1).abs() < 1e-9;
+    let mut triangles: Vec<[Point; 3]> = Vec::new();
+    while path.len() > 3 {
+        let count = path.len();
+        let corners = |i: usize| (path[(i + count - 1) % count], path[i], path[(i + 1) % count]);
+        let ear = (0..count).find(|i| {
+            let (a, b, c) = corners(*i);
+            cross(a, b, c) > 1e-9
+                && !path.iter().any(|p| {
+                    !same(p, &a)
+                        && !same(p, &b)
+                        && !same(p, &c)
+                        && cross(a, b, *p) >= -1e-9
+                        && cross(b, c, *p) >= -1e-9
+                        && cross(c, a, *p) >= -1e-9
+                })
+        });
+        // rounding can leave no clean ear; the sharpest corner is the least harm
+        let ear = ear.or_else(|| {
+            (0..count)
+                .filter(|i| {
+                    let (a, b, c) = corners(*i);
+                    cross(a, b, c) > 1e-9
+                })
+                .max_by(|i, j| {
+                    let ((a, b, c), (d, e, f)) = (corners(*i), corners(*j));
+                    cross(a, b, c).total_cmp(&cross(d, e, f))
+                })
+        });
+        let Some(i) = ear else {
+            break; // nothing left with any area
+        };
+        let (a, b, c) = corners(i);
+        triangles.push([a, b, c]);
+        path.remove(i);
+    }
+    if let [a, b, c] = path[..]
+        && cross(a, b, c) > 1e-9
+    {
+        triangles.push([a, b, c]);
+    }
+    triangles
+}
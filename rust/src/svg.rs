@@ -0,0 +1,127 @@
+use std::fmt::Write;
+
+use crate::common::{Coordinate, CustomBoard, Solution};
+use crate::drawing::{Drawing, Point, Style, center, label_size};
+
+// draws a board as an SVG image: every cell with its label, the cells the target leaves uncovered
+// shaded, and (for a solution) each piece filled in and outlined
+pub struct SvgRenderer {
+    cell_size: f64, // in pixels, between neighbouring cells
+    style: Style,
+}
+
+impl SvgRenderer {
+    pub fn new(style: Style) -> SvgRenderer {
+        SvgRenderer { cell_size: 40.0, style }
+    }
+
+    // just the board, with every cell labelled
+    pub fn board(&self, board: &dyn CustomBoard) -> String {
+        self.render(&Drawing::new(board, &[], &[], self.cell_size, self.margin()))
+    }
+
+    // the board as it is before any pieces go down, with the target cells shaded
+    #[allow(dead_code)]
+    pub fn puzzle(&self, board: &dyn CustomBoard, target: &[Coordinate]) -> String {
+        self.render(&Drawing::new(board, target, &[], self.cell_size, self.margin()))
+    }
+
+    pub fn solution(&self, board: &dyn CustomBoard, solution: &Solution) -> String {
+        self.render(&Drawing::solution(board, solution, &self.style.hidden, self.cell_size, self.margin()))
+    }
+
+    fn margin(&self) -> f64 {
+        self.cell_size / 4.0
+    }
+
+    fn render(&self, drawing: &Drawing) -> String {
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {:.2} {:.2}" width="{:.0}" height="{:.0}">"#,
+            drawing.width, drawing.height, drawing.width, drawing.height
+        );
+        let _ = writeln!(svg, "<title>{}</title>", escape(&drawing.name));
+
+        // the cells, with the target ones shaded
+        let _ = writeln!(svg, r##"<g class="cells" stroke="#BBBBBB" stroke-width="1">"##);
+        for cell in drawing.cells.iter() {
+            let fill = if cell.target { escape(&self.style.target_color) } else { "#FFFFFF".to_string() };
+            let _ = writeln!(svg, r#"<polygon points="{}" fill="{}"/>"#, points_attribute(&cell.polygon), fill);
+        }
+        let _ = writeln!(svg, "</g>");
+
+        if self.style.labels {
+            let _ = writeln!(
+                svg,
+                r##"<g class="labels" font-family="sans-serif" text-anchor="middle" dominant-baseline="central" fill="#444444">"##
+            );
+            for cell in drawing.cells.iter() {
+                if let Some(label) = &cell.label {
+                    let (x, y) = center(&cell.polygon);
+                    let size = self.cell_size * label_size(label);
+                    let _ = writeln!(
+                        svg,
+                        r#"<text x="{:.2}" y="{:.2}" font-size="{:.1}">{}</text>"#,
+                        x,
+                        y,
+                        size,
+                        escape(label)
+                    );
+                }
+            }
+            let _ = writeln!(svg, "</g>");
+        }
+
+        // each piece is filled in cell by cell, then outlined
+        let _ = writeln!(svg, r#"<g class="pieces">"#);
+        for piece in drawing.pieces.iter() {
+            // colours come from the command line or the page, so they're escaped like any other text
+            let color = escape(&self.style.colors[piece.piece % self.style.colors.len()]);
+            let _ = writeln!(svg, r#"<g data-piece="{}" data-copy="{}">"#, piece.piece, piece.copy);
+            for cell in piece.cells.iter() {
+                let _ = writeln!(
+                    svg,
+                    r#"<polygon points="{}" fill="{}" stroke="{}" stroke-width="0.5"/>"#,
+                    points_attribute(cell),
+                    color,
+                    color
+                );
+            }
+            let _ = writeln!(
+                svg,
+                r##"<path d="{}" fill="none" stroke="#222222" stroke-width="{:.1}" stroke-linecap="round"/>"##,
+                path_data(&piece.outline),
+                self.cell_size / 16.0
+            );
+            let _ = writeln!(svg, "</g>");
+        }
+        let _ = writeln!(svg, "</g>");
+
+        let _ = writeln!(
+            svg,
+            r##"<path class="border" d="{}" fill="none" stroke="#222222" stroke-width="{:.1}" stroke-linecap="round"/>"##,
+            path_data(&drawing.border),
+            self.cell_size / 12.0
+        );
+        let _ = writeln!(svg, "</svg>");
+        svg
+    }
+}
+
+fn path_data(edges: &[(Point, Point)]) -> String {
+    let mut path = String::new();
+    for (from, to) in edges {
+        let _ = write!(path, "M{:.2} {:.2}L{:.2} {:.2}", from.0, from.1, to.0, to.1);
+    }
+    path
+}
+
+fn points_attribute(polygon: &[Point]) -> String {
+    let points: Vec<String> = polygon.iter().map(|(x, y)| format!("{:.2},{:.2}", x, y)).collect();
+    points.join(" ")
+}
+
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
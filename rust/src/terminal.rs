@@ -0,0 +1,276 @@
+use std::collections::HashMap;
+use std::io::IsTerminal;
+
+use crate::common::{Coordinate, CustomBoard, Solution};
+use crate::drawing::Style;
+use crate::lattice::Lattice;
+
+// the piece each marker stands for is listed under the board
+const TILE_MARKERS: [char; 16] = [
+    'I', 'O', 'B', 'S', 'X', '2', 'N', 'V', 'Y', '7', 'J', 'T', '9', 'W', 'K', 'L',
+];
+
+// draws a solution for the terminal: on square boards, pieces are outlined with box-drawing characters
+// and filled with their colour (on other boards, each cell shows its piece's marker), the cells left
+// uncovered show their labels, and hidden pieces leave empty board cells behind. Colour is only used
+// when writing to a terminal (and NO_COLOR isn't set)
+pub struct TerminalRenderer {
+    colors: Vec<(u8, u8, u8)>, // the style's colours, by piece index
+    color: bool,
+    hidden: Vec<usize>, // indices into the solution's pieces; these are left off
+    all_labels: bool,   // label every empty cell, not just the target ones
+}
+
+// what a cell of the board shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Region {
+    Piece(usize),      // covered by the piece at this index in the solution
+    Empty(Coordinate), // nothing on it (yet); every empty cell is outlined on its own
+}
+
+impl TerminalRenderer {
+    pub fn new(style: Style) -> TerminalRenderer {
+        TerminalRenderer {
+            colors: style.rgb_colors(),
+            color: std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none(),
+            hidden: style.hidden,
+            all_labels: false,
+        }
+    }
+
+    // for a hint sheet: the cells under hidden pieces show their labels too, and the target ones are
+    // bracketed, eg. "[Oct]", so they still stand out on paper
+    pub fn with_all_labels(mut self, all_labels: bool) -> TerminalRenderer {
+        self.all_labels = all_labels;
+        self
+    }
+
+    // the board, then a legend with the marker (and colour) of each piece shown
+    pub fn solution(&self, board: &dyn CustomBoard, solution: &Solution) -> String {
+        let mut lines = match board.lattice() {
+            Lattice::Square => self.boxes(board, solution),
+            _ => self.markers(board, solution),
+        };
+
+        for (i, placed) in solution.pieces.iter().enumerate() {
+            if self.hidden.contains(&i) {
+                continue;
+            }
+            let swatch = self.paint(&TILE_MARKERS[i % TILE_MARKERS.len()].to_string(), Some(i), solution);
+            match &placed.name {
+                Some(name) => lines.push(format!("{}: piece {} ({})", swatch, placed.piece, name)),
+                None => lines.push(format!("{}: piece {}", swatch, placed.piece)),
+            }
+        }
+
+        let mut output = lines.join("\n");
+        output.push('\n');
+        output
+    }
+
+    // a grid of box-drawing lines, with a line wherever the cells on either side show different things
+    fn boxes(&self, board: &dyn CustomBoard, solution: &Solution) -> Vec<String> {
+        let coors = board.coors();
+        let labels = board.labels();
+        let mut regions: HashMap<Coordinate, Region> = coors.iter().map(|coor| (*coor, Region::Empty(*coor))).collect();
+        for (i, placed) in solution.pieces.iter().enumerate() {
+            if self.hidden.contains(&i) {
+                continue;
+            }
+            for coor in placed.tile.iter() {
+                regions.insert(*coor, Region::Piece(i));
+            }
+        }
+        let uncovered = |coor: &Coordinate| solution.target.contains(coor) || solution.gaps.contains(coor);
+
+        let min_x = coors.iter().map(|coor| coor.x).min().unwrap_or(0);
+        let min_y = coors.iter().map(|coor| coor.y).min().unwrap_or(0);
+        let max_x = coors.iter().map(|coor| coor.x).max().unwrap_or(-1);
+        let max_y = coors.iter().map(|coor| coor.y).max().unwrap_or(-1);
+        let region = |x: i32, y: i32| regions.get(&Coordinate { x, y }).copied();
+        let piece_of = |region: Option<Region>| match region {
+            Some(Region::Piece(i)) => Some(i),
+            _ => None,
+        };
+
+        // wide enough for the longest label, with a space on either side
+        let width = labels.values().map(|label| label.chars().count()).max().unwrap_or(1).max(1) + 2;
+
+        let mut lines: Vec<String> = Vec::new();
+        for y in min_y..=max_y + 1 {
+            // the line above row y
+            let mut line = String::new();
+            for x in min_x..=max_x + 1 {
+                let (up_left, up_right) = (region(x - 1, y - 1), region(x, y - 1));
+                let (down_left, down_right) = (region(x - 1, y), region(x, y));
+                let corner = box_corner(
+                    up_left != up_right,
+                    down_left != down_right,
+                    up_left != down_left,
+                    up_right != down_right,
+                );
+                match corner {
+                    ' ' => line += &self.paint(" ", piece_of(up_left), solution),
+                    corner => line.push(corner),
+                }
+                if x <= max_x {
+                    let (above, below) = (region(x, y - 1), region(x, y));
+                    if above != below {
+                        line += &"─".repeat(width);
+                    } else {
+                        line += &self.paint(&" ".repeat(width), piece_of(above), solution);
+                    }
+                }
+            }
+            lines.push(line.trim_end().to_string());
+            if y > max_y {
+                break;
+            }
+
+            // row y itself
+            let mut line = String::new();
+            for x in min_x..=max_x + 1 {
+                let (left, right) = (region(x - 1, y), region(x, y));
+                if left != right {
+                    line.push('│');
+                } else {
+                    line += &self.paint(" ", piece_of(left), solution);
+                }
+                if x > max_x {
+                    continue;
+                }
+                let coor = Coordinate { x, y };
+                match right {
+                    Some(Region::Piece(i)) => {
+                        let marker = TILE_MARKERS[i % TILE_MARKERS.len()].to_string();
+                        line += &self.paint(&centered(&marker, width), Some(i), solution);
+                    }
+                    Some(Region::Empty(_)) if uncovered(&coor) => {
+                        let label = labels.get(&coor).cloned().unwrap_or_default();
+                        let label = if self.all_labels { format!("[{}]", label) } else { label };
+                        line += &self.bold(&centered(&label, width));
+                    }
+                    Some(Region::Empty(_)) if self.all_labels => {
+                        line += &centered(labels.get(&coor).map(|label| label.as_str()).unwrap_or(""), width);
+                    }
+                    _ => line += &" ".repeat(width),
+                }
+            }
+            lines.push(line.trim_end().to_string());
+        }
+        lines
+    }
+
+    // hexagons and triangles don't fit in boxes, so each cell is printed wherever the lattice puts it in
+    // the text, as wide as the longest label: the marker of the piece on it, its label if the target
+    // leaves it uncovered (or it's empty and every label is shown), or '.' for an empty cell
+    fn markers(&self, board: &dyn CustomBoard, solution: &Solution) -> Vec<String> {
+        let lattice = board.lattice();
+        let labels = board.labels();
+        let mut pieces: HashMap<Coordinate, usize> = HashMap::new();
+        for (i, placed) in solution.pieces.iter().enumerate() {
+            if self.hidden.contains(&i) {
+                continue;
+            }
+            for coor in placed.tile.iter() {
+                pieces.insert(*coor, i);
+            }
+        }
+        let uncovered = |coor: &Coordinate| solution.target.contains(coor) || solution.gaps.contains(coor);
+
+        let mut cells: Vec<((i32, i32), String)> = Vec::new();
+        // with every label shown, the target ones are bracketed (as on square boards)
+        let brackets = if self.all_labels { 2 } else { 0 };
+        let width = labels.values().map(|label| label.chars().count() + brackets).max().unwrap_or(1).max(1);
+        for coor in board.coors().iter() {
+            let label = labels.get(coor);
+            let text = match (pieces.get(coor), label) {
+                (Some(i), _) => {
+                    let marker = TILE_MARKERS[i % TILE_MARKERS.len()].to_string();
+                    self.paint(&centered(&marker, width), Some(*i), solution)
+                }
+                (None, Some(label)) if uncovered(coor) && self.all_labels => {
+                    self.bold(&centered(&format!("[{}]", label), width))
+                }
+                (None, Some(label)) if uncovered(coor) || self.all_labels => {
+                    let text = centered(label, width);
+                    if uncovered(coor) { self.bold(&text) } else { text }
+                }
+                (None, _) => centered(".", width),
+            };
+            let (column, row) = lattice.text_position(coor);
+            cells.push(((row, column), text));
+        }
+        cells.sort_by_key(|(position, _)| *position);
+
+        // neighbouring cells in a row can be a single column apart, so every column is made wide enough
+        // for the cells to keep a space between them
+        let step = lattice.text_position(&Coordinate { x: 1, y: 0 }).0 - lattice.text_position(&Coordinate { x: 0, y: 0 }).0;
+        let column_width = (width + 1).div_ceil(step.max(1) as usize);
+        let min_column = cells.iter().map(|((_, column), _)| *column).min().unwrap_or(0);
+        let min_row = cells.iter().map(|((row, _), _)| *row).min().unwrap_or(0);
+        let max_row = cells.iter().map(|((row, _), _)| *row).max().unwrap_or(-1);
+
+        let mut lines: Vec<String> = Vec::new();
+        for row in min_row..=max_row {
+            let mut line = String::new();
+            let mut at = 0;
+            for ((_, column), text) in cells.iter().filter(|((cell_row, _), _)| *cell_row == row) {
+                let start = (column - min_column) as usize * column_width;
+                line += &" ".repeat(start.saturating_sub(at));
+                line += text;
+                at = start.max(at) + width;
+            }
+            lines.push(line.trim_end().to_string());
+        }
+        lines
+    }
+
+    // with the background colour of the piece at this index in the solution (if any)
+    fn paint(&self, text: &str, piece: Option<usize>, solution: &Solution) -> String {
+        match piece {
+            Some(i) if self.color => {
+                let (r, g, b) = self.colors[solution.pieces[i].piece % self.colors.len()];
+                format!("\x1b[30;48;2;{};{};{}m{}\x1b[0m", r, g, b, text)
+            }
+            _ => text.to_string(),
+        }
+    }
+
+    fn bold(&self, text: &str) -> String {
+        if self.color {
+            format!("\x1b[1m{}\x1b[0m", text)
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+// the box-drawing character for the lines leaving a corner
+fn box_corner(up: bool, down: bool, left: bool, right: bool) -> char {
+    match (up, down, left, right) {
+        (false, false, false, false) => ' ',
+        (true, true, false, false) => '│',
+        (false, false, true, true) => '─',
+        (false, true, false, true) => '┌',
+        (false, true, true, false) => '┐',
+        (true, false, false, true) => '└',
+        (true, false, true, false) => '┘',
+        (true, true, false, true) => '├',
+        (true, true, true, false) => '┤',
+        (false, true, true, true) => '┬',
+        (true, false, true, true) => '┴',
+        (true, true, true, true) => '┼',
+        (true, false, false, false) => '╵',
+        (false, true, false, false) => '╷',
+        (false, false, true, false) => '╴',
+        (false, false, false, true) => '╶',
+    }
+}
+
+fn centered(text: &str, width: usize) -> String {
+    let length = text.chars().count();
+    let left = width.saturating_sub(length) / 2;
+    let right = width.saturating_sub(length + left);
+    format!("{}{}{}", " ".repeat(left), text, " ".repeat(right))
+}
@@ -0,0 +1,148 @@
+use std::fmt::Write;
+
+use crate::common::{CustomBoard, Solution};
+use crate::drawing::{Drawing, Point, Style, center, label_size};
+
+// a centimetre, in TeX points
+const POINTS_PER_CM: f64 = 28.4528;
+
+// draws a board as a tikzpicture, the same way SvgRenderer does, for documents typeset with LaTeX: the
+// output can be \input as it is, and only needs \usepackage{tikz}. Colours are defined inside the
+// picture, so they don't clash with the document's
+pub struct TikzRenderer {
+    cell_size: f64,            // in centimetres, between neighbouring cells
+    colors: Vec<(u8, u8, u8)>, // the style's colours, by piece index
+    style: Style,
+}
+
+impl TikzRenderer {
+    pub fn new(style: Style) -> TikzRenderer {
+        TikzRenderer {
+            cell_size: 1.0,
+            colors: style.rgb_colors(),
+            style,
+        }
+    }
+
+    pub fn board(&self, board: &dyn CustomBoard) -> String {
+        self.render(&Drawing::new(board, &[], &[], self.cell_size, 0.0))
+    }
+
+    pub fn solution(&self, board: &dyn CustomBoard, solution: &Solution) -> String {
+        self.render(&Drawing::solution(board, solution, &self.style.hidden, self.cell_size, 0.0))
+    }
+
+    // the drawing is in centimetres with y pointing down, so the picture's y axis is flipped to match
+    fn render(&self, drawing: &Drawing) -> String {
+        let mut tikz = String::new();
+        let _ = writeln!(tikz, "% {}", escape(&drawing.name));
+        let _ = writeln!(tikz, "\\begin{{tikzpicture}}[x=1cm, y=-1cm, line join=round]");
+        let _ = writeln!(tikz, "\\definecolor{{padgrid}}{{RGB}}{{187,187,187}}");
+        let _ = writeln!(tikz, "\\definecolor{{padink}}{{RGB}}{{34,34,34}}");
+        let _ = writeln!(tikz, "\\definecolor{{padlabel}}{{RGB}}{{68,68,68}}");
+        let _ = writeln!(tikz, "\\definecolor{{padtarget}}{{RGB}}{{{}}}", rgb(self.style.target_rgb()));
+        let mut used: Vec<usize> = drawing.pieces.iter().map(|piece| piece.piece % self.colors.len()).collect();
+        used.sort();
+        used.dedup();
+        for i in used {
+            let _ = writeln!(tikz, "\\definecolor{{padpiece{}}}{{RGB}}{{{}}}", i, rgb(self.colors[i]));
+        }
+
+        for cell in drawing.cells.iter() {
+            let fill = if cell.target { "padtarget" } else { "white" };
+            let _ = writeln!(
+                tikz,
+                "\\filldraw[fill={}, draw=padgrid, line width=0.4pt] {};",
+                fill,
+                polygon_path(&cell.polygon)
+            );
+        }
+
+        if self.style.labels {
+            for cell in drawing.cells.iter() {
+                if let Some(label) = &cell.label {
+                    let (x, y) = center(&cell.polygon);
+                    let size = self.cell_size * POINTS_PER_CM * label_size(label);
+                    let _ = writeln!(
+                        tikz,
+                        "\\node[text=padlabel, inner sep=0pt, font=\\sffamily\\fontsize{{{:.1}pt}}{{{:.1}pt}}\\selectfont] at ({:.3}, {:.3}) {{{}}};",
+                        size,
+                        size,
+                        x,
+                        y,
+                        escape(label)
+                    );
+                }
+            }
+        }
+
+        // pieces are stroked in their own colour too, so no seams show between their cells
+        for piece in drawing.pieces.iter() {
+            let color = format!("padpiece{}", piece.piece % self.colors.len());
+            let _ = writeln!(tikz, "% piece {}, copy {}", piece.piece, piece.copy);
+            for cell in piece.cells.iter() {
+                let _ = writeln!(
+                    tikz,
+                    "\\filldraw[fill={}, draw={}, line width=0.2pt] {};",
+                    color,
+                    color,
+                    polygon_path(cell)
+                );
+            }
+            let _ = writeln!(
+                tikz,
+                "\\draw[draw=padink, line width={:.3}cm, line cap=round] {};",
+                self.cell_size / 16.0,
+                edges_path(&piece.outline)
+            );
+        }
+
+        let _ = writeln!(
+            tikz,
+            "\\draw[draw=padink, line width={:.3}cm, line cap=round] {};",
+            self.cell_size / 12.0,
+            edges_path(&drawing.border)
+        );
+        let _ = writeln!(tikz, "\\end{{tikzpicture}}");
+        tikz
+    }
+}
+
+fn rgb((r, g, b): (u8, u8, u8)) -> String {
+    format!("{},{},{}", r, g, b)
+}
+
+fn point((x, y): Point) -> String {
+    format!("({:.3}, {:.3})", x, y)
+}
+
+fn polygon_path(polygon: &[Point]) -> String {
+    let points: Vec<String> = polygon.iter().map(|corner| point(*corner)).collect();
+    format!("{} -- cycle", points.join(" -- "))
+}
+
+fn edges_path(edges: &[(Point, Point)]) -> String {
+    let edges: Vec<String> = edges
+        .iter()
+        .map(|(from, to)| format!("{} -- {}", point(*from), point(*to)))
+        .collect();
+    edges.join(" ")
+}
+
+// the characters TeX treats specially, so labels come out as written
+fn escape(text: &str) -> String {
+    let mut escaped = String::new();
+    for ch in text.chars() {
+        match ch {
+            '\\' => escaped += "\\textbackslash{}",
+            '~' => escaped += "\\textasciitilde{}",
+            '^' => escaped += "\\textasciicircum{}",
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(ch);
+            }
+            ch => escaped.push(ch),
+        }
+    }
+    escaped
+}
@@ -1,4 +1,5 @@
-use crate::common::{Coordinate, Tile};
+use crate::common::{Coordinate, Orientation, Symmetry, Tile};
+use crate::lattice::Lattice;
 
 pub struct TileHelper {}
 
@@ -19,7 +20,7 @@ impl TileHelper {
 
     #[inline]
     // find difference between tile[0] and some origin
-    pub fn calc_offset(tile: &Tile, origin: &Coordinate) -> Coordinate {
+    pub fn calc_offset(tile: &[Coordinate], origin: &Coordinate) -> Coordinate {
         Coordinate {
             x: origin.x - tile[0].x,
             y: origin.y - tile[0].y,
@@ -28,7 +29,7 @@ impl TileHelper {
 
     #[inline]
     // move entire tile by offset
-    pub fn translate(tile: &mut Tile, offset: &Coordinate) {
+    pub fn translate(tile: &mut [Coordinate], offset: &Coordinate) {
         for coor in tile.iter_mut() {
             coor.x += offset.x;
             coor.y += offset.y;
@@ -55,10 +56,144 @@ impl TileHelper {
         }
     }
 
+    // all distinct orientations allowed by the symmetry, each one rotated / flipped around tile[0]
+    pub fn orientations(tile: &Tile, symmetry: Symmetry, lattice: Lattice) -> Vec<Tile> {
+        Self::oriented(tile, symmetry, lattice)
+            .into_iter()
+            .map(|(_, tile)| tile)
+            .collect()
+    }
+
+    // same as orientations, along with how each one was turned
+    pub fn oriented(tile: &Tile, symmetry: Symmetry, lattice: Lattice) -> Vec<(Orientation, Tile)> {
+        let mut candidates: Vec<(Orientation, Tile)> = Vec::with_capacity(12);
+        let sides: &[bool] = match symmetry {
+            Symmetry::Fixed | Symmetry::Rotations => &[false],
+            Symmetry::All => &[false, true],
+        };
+        let rotations = match symmetry {
+            Symmetry::Fixed => 1,
+            Symmetry::Rotations | Symmetry::All => lattice.rotation_count(),
+        };
+        for flipped in sides {
+            let mut current_tile = tile.clone();
+            if *flipped {
+                lattice.flip(&mut current_tile);
+            }
+            for rotation in 0..rotations {
+                candidates.push((
+                    Orientation {
+                        rotation,
+                        flipped: *flipped,
+                    },
+                    current_tile.clone(),
+                ));
+                lattice.rotate(&mut current_tile);
+            }
+        }
+
+        // symmetric tiles look the same in several orientations, only keep one of each
+        // (otherwise the solver finds every placement of such a tile more than once)
+        let mut orientations: Vec<(Orientation, Tile)> = Vec::with_capacity(candidates.len());
+        for (orientation, candidate) in candidates {
+            if !orientations.iter().any(|(_, o)| lattice.same_shape(o, &candidate)) {
+                orientations.push((orientation, candidate));
+            }
+        }
+        orientations
+    }
+
+    // sorted coordinates, moved so the smallest x and y are both 0
+    pub fn normalized(tile: &Tile) -> Tile {
+        let min_x = tile.iter().map(|coor| coor.x).min().unwrap_or(0);
+        let min_y = tile.iter().map(|coor| coor.y).min().unwrap_or(0);
+        let mut out: Tile = tile
+            .iter()
+            .map(|coor| Coordinate {
+                x: coor.x - min_x,
+                y: coor.y - min_y,
+            })
+            .collect();
+        out.sort_by_key(|coor| (coor.y, coor.x));
+        out
+    }
+
+    // parse a piece from ascii art ('#' for a cell, '.' or ' ' for a gap, rows split by newlines or '/'),
+    // or from a list of coordinates like "0,0 1,0 2,0 2,1"; either way, in the lattice's own coordinates
+    #[allow(dead_code)]
+    pub fn parse(spec: &str, lattice: Lattice) -> Option<Tile> {
+        let tile = if spec.chars().any(|ch| ch.is_ascii_digit()) {
+            Self::parse_coordinates(spec)?
+        } else {
+            Self::parse_ascii_art(spec)?
+        };
+
+        if tile.is_empty() || !Self::is_connected(&tile, lattice) {
+            return None;
+        }
+        for (i, coor) in tile.iter().enumerate() {
+            if tile[..i].contains(coor) {
+                return None;
+            }
+        }
+        Some(lattice.normalized(&tile))
+    }
+
+    fn parse_coordinates(spec: &str) -> Option<Tile> {
+        // anything that isn't part of a number just separates numbers, so "(0, 0) (1, 0)" works too
+        let cleaned: String = spec
+            .chars()
+            .map(|ch| if ch.is_ascii_digit() || ch == '-' { ch } else { ' ' })
+            .collect();
+        let mut numbers: Vec<i32> = Vec::new();
+        for number in cleaned.split_whitespace() {
+            numbers.push(number.parse().ok()?);
+        }
+        if !numbers.len().is_multiple_of(2) {
+            return None;
+        }
+        Some(numbers.chunks(2).map(|pair| Coordinate { x: pair[0], y: pair[1] }).collect())
+    }
+
+    fn parse_ascii_art(spec: &str) -> Option<Tile> {
+        let mut tile: Tile = Vec::new();
+        for (y, row) in spec.split(['\n', '/']).enumerate() {
+            for (x, ch) in row.trim_end().chars().enumerate() {
+                match ch {
+                    '#' | 'X' | 'x' | 'O' | 'o' => tile.push(Coordinate {
+                        x: x as i32,
+                        y: y as i32,
+                    }),
+                    '.' | ' ' | '_' => {}
+                    _ => return None,
+                }
+            }
+        }
+        Some(tile)
+    }
+
+    // can every cell of the tile be reached from tile[0] through its neighbours?
+    pub fn is_connected(tile: &Tile, lattice: Lattice) -> bool {
+        let mut reached: Vec<Coordinate> = Vec::with_capacity(tile.len());
+        let mut stack: Vec<Coordinate> = tile.iter().take(1).copied().collect();
+        while let Some(current) = stack.pop() {
+            if reached.contains(&current) {
+                continue;
+            }
+            reached.push(current);
+            for neighbor in lattice.neighbors(&current) {
+                if tile.contains(&neighbor) {
+                    stack.push(neighbor);
+                }
+            }
+        }
+        reached.len() == tile.len()
+    }
+
     #[inline]
     // is the tile completely within the 16x16 board?
     // aka will we avoid an index out of bounds error?
-    pub fn is_within_board(tile: &Tile) -> bool {
+    pub fn is_within_board(tile: &[Coordinate]) -> bool {
         for coor in tile {
             if coor.x < 0 || coor.x >= 16 || coor.y < 0 || coor.y >= 16 {
                 return false;